@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+
+use super::{BDFFont, Glyph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingConflict {
+    /// The encoding shared by the glyphs.
+    pub encoding: char,
+    /// Indices of all glyphs with this encoding.
+    pub glyph_indices: Vec<usize>,
+    /// `true` if all glyphs have the same bounding box and bitmap.
+    pub all_identical: bool,
+}
+
+impl BDFFont {
+    /// Finds encodings that are used by more than one glyph, sorted by encoding.
+    ///
+    /// Identical duplicates are usually harmless aliases, while duplicates with different
+    /// bitmaps make the rendered glyph depend on which one a reader picks.
+    pub fn detect_encoding_conflicts(&self) -> Vec<EncodingConflict> {
+        let mut by_encoding: BTreeMap<char, Vec<usize>> = BTreeMap::new();
+
+        for (index, glyph) in self.glyphs.iter().enumerate() {
+            if let Some(encoding) = glyph.encoding() {
+                by_encoding.entry(encoding).or_default().push(index);
+            }
+        }
+
+        by_encoding
+            .into_iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(encoding, glyph_indices)| {
+                let first = &self.glyphs[glyph_indices[0]];
+                let all_identical = glyph_indices[1..].iter().all(|&index| {
+                    let glyph = &self.glyphs[index];
+                    glyph.bounding_box == first.bounding_box && glyph.bitmap == first.bitmap
+                });
+
+                EncodingConflict {
+                    encoding,
+                    glyph_indices,
+                    all_identical,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the average pixel density of all encoded glyphs, or `0.0` if there are none.
+    ///
+    /// Bold fonts score higher than regular fonts, which in turn score higher than light or
+    /// outlined fonts.
+    pub fn bitmap_complexity_score(&self) -> f32 {
+        let densities: Vec<f32> = self
+            .glyphs
+            .iter()
+            .filter(|glyph| glyph.encoding().is_some())
+            .map(|glyph| glyph.pixel_density())
+            .collect();
+
+        if densities.is_empty() {
+            0.0
+        } else {
+            densities.iter().sum::<f32>() / densities.len() as f32
+        }
+    }
+
+    /// Returns the number of encoded glyphs in each of the inclusive char ranges.
+    ///
+    /// The encodings are sorted once, which makes this faster than counting the glyphs of each
+    /// range separately when many ranges are queried.
+    pub fn count_glyphs_by_range(&self, ranges: &[(char, char)]) -> Vec<usize> {
+        let mut encodings: Vec<char> = self
+            .glyphs
+            .iter()
+            .filter_map(|glyph| glyph.encoding())
+            .collect();
+        encodings.sort_unstable();
+
+        ranges
+            .iter()
+            .map(|&(start, end)| {
+                let first = encodings.partition_point(|&c| c < start);
+                let last = encodings.partition_point(|&c| c <= end);
+
+                last.saturating_sub(first)
+            })
+            .collect()
+    }
+
+    /// Returns `count` different glyphs, picked pseudo-randomly based on `seed`.
+    ///
+    /// The same seed always returns the same glyphs, in the order they appear in the font. If
+    /// the font contains no more than `count` glyphs, all glyphs are returned.
+    pub fn sample_glyphs(&self, count: usize, seed: u64) -> Vec<&Glyph> {
+        let mut indices: Vec<usize> = (0..self.glyphs.len()).collect();
+        let count = count.min(indices.len());
+        let mut state = seed;
+
+        // Partial Fisher-Yates shuffle using the wyrand generator
+        for i in 0..count {
+            state = state.wrapping_add(0xa076_1d64_78bd_642f);
+            let t = u128::from(state) * u128::from(state ^ 0xe703_7ed1_a0b4_28db);
+            let random = ((t >> 64) as u64) ^ (t as u64);
+
+            let remaining = (indices.len() - i) as u128;
+            let j = i + ((u128::from(random) * remaining) >> 64) as usize;
+            indices.swap(i, j);
+        }
+
+        indices.truncate(count);
+        indices.sort_unstable();
+
+        indices.into_iter().map(|i| &self.glyphs[i]).collect()
+    }
+
+    /// Returns all glyphs whose bitmap differs from `glyph` in at most `threshold` of the
+    /// pixels.
+    ///
+    /// The bitmaps are aligned at their origin and compared within the union of both bounding
+    /// boxes. Glyphs with the same name and encoding as `glyph` are considered to be `glyph`
+    /// itself and aren't included, even if `glyph` is a copy or comes from another font.
+    pub fn find_visually_similar(&self, glyph: &Glyph, threshold: f32) -> Vec<&Glyph> {
+        self.glyphs
+            .iter()
+            .filter(|other| other.name != glyph.name || other.charcode != glyph.charcode)
+            .filter(|other| bitmap_distance(glyph, other) <= threshold)
+            .collect()
+    }
+}
+
+/// Returns the fraction of pixels that differ between two glyphs.
+fn bitmap_distance(a: &Glyph, b: &Glyph) -> f32 {
+    let left = a.bounding_box.2.min(b.bounding_box.2);
+    let right = (a.bounding_box.2 + a.bounding_box.0 as i32)
+        .max(b.bounding_box.2 + b.bounding_box.0 as i32);
+    let bottom = a.bounding_box.3.min(b.bounding_box.3);
+    let top = (a.bounding_box.3 + a.bounding_box.1 as i32)
+        .max(b.bounding_box.3 + b.bounding_box.1 as i32);
+
+    let area = (right - left) * (top - bottom);
+    if area == 0 {
+        return 0.0;
+    }
+
+    // Returns the pixel at (x, y) relative to the origin, with the y axis pointing up
+    let pixel = |glyph: &Glyph, x: i32, y: i32| {
+        let (_, height, x_offset, y_offset) = glyph.bounding_box;
+        let (x, row) = (x - x_offset, y_offset + height as i32 - 1 - y);
+
+        x >= 0 && row >= 0 && glyph.get_pixel(x as u32, row as u32) == Some(true)
+    };
+
+    let differences = (bottom..top)
+        .flat_map(|y| (left..right).map(move |x| (x, y)))
+        .filter(|&(x, y)| pixel(a, x, y) != pixel(b, x, y))
+        .count();
+
+    differences as f32 / area as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "analysis"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 2 0 0
+STARTCHAR A
+ENCODING 65
+BBX 8 2 0 0
+BITMAP
+18
+24
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+BBX 8 2 0 0
+BITMAP
+7c
+42
+ENDCHAR
+STARTCHAR A.alias
+ENCODING 65
+BBX 8 2 0 0
+BITMAP
+18
+24
+ENDCHAR
+STARTCHAR B.alt
+ENCODING 66
+BBX 8 2 0 0
+BITMAP
+7e
+42
+ENDCHAR
+STARTCHAR C
+ENCODING 67
+BBX 8 2 0 0
+BITMAP
+3c
+40
+ENDCHAR
+ENDFONT
+"#;
+
+    fn font() -> BDFFont {
+        BDFParser::from_str(FONT).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_detects_encoding_conflicts() {
+        assert_eq!(
+            font().detect_encoding_conflicts(),
+            vec![
+                EncodingConflict {
+                    encoding: 'A',
+                    glyph_indices: vec![0, 2],
+                    all_identical: true,
+                },
+                EncodingConflict {
+                    encoding: 'B',
+                    glyph_indices: vec![1, 3],
+                    all_identical: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_scores_bitmap_complexity() {
+        let mut font = font();
+        assert!((font.bitmap_complexity_score() - 0.35).abs() < 1e-6);
+
+        // Unencoded glyphs are ignored
+        font.glyphs[4].charcode = -1;
+        assert!((font.bitmap_complexity_score() - 0.359375).abs() < 1e-6);
+
+        font.glyphs.clear();
+        assert_eq!(font.bitmap_complexity_score(), 0.0);
+    }
+
+    #[test]
+    fn it_counts_glyphs_by_range() {
+        assert_eq!(
+            font().count_glyphs_by_range(&[
+                ('A', 'B'),
+                ('A', 'Z'),
+                ('C', 'C'),
+                ('a', 'z'),
+                ('Z', 'A')
+            ]),
+            vec![4, 5, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn it_samples_glyphs() {
+        let mut font = font();
+        let glyph = font.glyphs[0].clone();
+        font.glyphs = (0..100)
+            .map(|charcode| Glyph {
+                charcode,
+                ..glyph.clone()
+            })
+            .collect();
+
+        let charcodes = |glyphs: Vec<&Glyph>| -> Vec<i32> {
+            glyphs.iter().map(|glyph| glyph.charcode).collect()
+        };
+
+        let sample = charcodes(font.sample_glyphs(10, 42));
+        assert_eq!(sample.len(), 10);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(charcodes(font.sample_glyphs(10, 42)), sample);
+        assert_ne!(charcodes(font.sample_glyphs(10, 43)), sample);
+
+        assert_eq!(font.sample_glyphs(200, 1).len(), 100);
+        assert!(font.sample_glyphs(0, 1).is_empty());
+    }
+
+    #[test]
+    fn it_finds_visually_similar_glyphs() {
+        let font = font();
+        let names = |glyphs: Vec<&Glyph>| -> Vec<String> {
+            glyphs.iter().map(|glyph| glyph.name.clone()).collect()
+        };
+
+        assert_eq!(
+            names(font.find_visually_similar(&font.glyphs[1], 0.125)),
+            vec!["B.alt", "C"]
+        );
+        assert_eq!(
+            names(font.find_visually_similar(&font.glyphs[0], 0.0)),
+            vec!["A.alias"]
+        );
+
+        let copy = font.glyphs[0].clone();
+        assert_eq!(
+            names(font.find_visually_similar(&copy, 0.0)),
+            vec!["A.alias"]
+        );
+    }
+
+    #[test]
+    fn it_aligns_glyphs_at_their_origin() {
+        let font = font();
+        let mut shifted = font.glyphs[0].clone();
+        shifted.bounding_box.2 = 1;
+
+        assert_eq!(bitmap_distance(&font.glyphs[0], &shifted), 6.0 / 18.0);
+        assert_eq!(bitmap_distance(&shifted, &shifted), 0.0);
+    }
+}
@@ -0,0 +1,209 @@
+use super::{BDFFont, BoundingBox};
+
+/// Glyph with 8 bit coverage values instead of a 1 bit bitmap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AntiAliasedGlyph {
+    pub name: String,
+    pub charcode: i32,
+    pub device_width: Option<(u32, u32)>,
+    pub bounding_box: BoundingBox,
+    /// Coverage of each pixel, row by row, from `0` for unset to `255` for fully set pixels.
+    pub pixels: Vec<u8>,
+}
+
+/// Font with anti-aliased glyphs created by [`BDFFont::apply_gamma`].
+///
+/// [`BDFFont::apply_gamma`]: struct.BDFFont.html#method.apply_gamma
+#[derive(Debug, Clone, PartialEq)]
+pub struct AntiAliasedFont {
+    pub glyphs: Vec<AntiAliasedGlyph>,
+}
+
+impl AntiAliasedFont {
+    /// Returns the glyph for the given character.
+    pub fn glyph(&self, c: char) -> Option<&AntiAliasedGlyph> {
+        self.glyphs.iter().find(|glyph| glyph.charcode == c as i32)
+    }
+}
+
+impl BDFFont {
+    /// Creates anti-aliased versions of all glyphs.
+    ///
+    /// Every glyph is upscaled by a factor of 4 with two passes of the Scale2x algorithm, which
+    /// smooths diagonal edges, and downsampled again by averaging each 4x4 block. The coverage
+    /// is gamma encoded with `gamma`, where `1.0` keeps the coverage linear.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` isn't positive.
+    pub fn apply_gamma(&self, gamma: f32) -> AntiAliasedFont {
+        assert!(gamma > 0.0, "gamma must be positive");
+
+        let glyphs = self
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let (width, height, _, _) = glyph.bounding_box;
+                let (width, height) = (width as usize, height as usize);
+
+                let mut pixels = Vec::with_capacity(width * height);
+                for y in 0..height {
+                    for x in 0..width {
+                        pixels.push(glyph.get_pixel(x as u32, y as u32) == Some(true));
+                    }
+                }
+
+                let pixels = scale2x(&pixels, width, height);
+                let pixels = scale2x(&pixels, width * 2, height * 2);
+
+                let coverage = (0..height)
+                    .flat_map(|y| (0..width).map(move |x| (x, y)))
+                    .map(|(x, y)| {
+                        let set = (0..4)
+                            .flat_map(|dy| (0..4).map(move |dx| (dx, dy)))
+                            .filter(|&(dx, dy)| pixels[(y * 4 + dy) * width * 4 + x * 4 + dx])
+                            .count();
+
+                        ((set as f32 / 16.0).powf(1.0 / gamma) * 255.0).round() as u8
+                    })
+                    .collect();
+
+                AntiAliasedGlyph {
+                    name: glyph.name.clone(),
+                    charcode: glyph.charcode,
+                    device_width: glyph.device_width,
+                    bounding_box: glyph.bounding_box,
+                    pixels: coverage,
+                }
+            })
+            .collect();
+
+        AntiAliasedFont { glyphs }
+    }
+}
+
+/// Doubles the size of a bitmap with the Scale2x algorithm.
+fn scale2x(pixels: &[bool], width: usize, height: usize) -> Vec<bool> {
+    let pixel = |x: isize, y: isize| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && pixels[y as usize * width + x as usize]
+    };
+
+    let mut out = vec![false; width * height * 4];
+
+    for y in 0..height {
+        for x in 0..width {
+            let (xi, yi) = (x as isize, y as isize);
+            let p = pixel(xi, yi);
+            let above = pixel(xi, yi - 1);
+            let right = pixel(xi + 1, yi);
+            let left = pixel(xi - 1, yi);
+            let below = pixel(xi, yi + 1);
+
+            let top_left = if left == above && left != below && above != right {
+                above
+            } else {
+                p
+            };
+            let top_right = if above == right && above != left && right != below {
+                right
+            } else {
+                p
+            };
+            let bottom_left = if below == left && below != right && left != above {
+                left
+            } else {
+                p
+            };
+            let bottom_right = if right == below && right != above && below != left {
+                below
+            } else {
+                p
+            };
+
+            let (ox, oy) = (x * 2, y * 2);
+            out[oy * width * 2 + ox] = top_left;
+            out[oy * width * 2 + ox + 1] = top_right;
+            out[(oy + 1) * width * 2 + ox] = bottom_left;
+            out[(oy + 1) * width * 2 + ox + 1] = bottom_right;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "antialias"
+SIZE 8 75 75
+FONTBOUNDINGBOX 3 3 0 0
+STARTCHAR slash
+ENCODING 92
+BBX 3 3 0 0
+BITMAP
+80
+40
+20
+ENDCHAR
+STARTCHAR block
+ENCODING 35
+BBX 4 4 0 0
+BITMAP
+F0
+F0
+F0
+F0
+ENDCHAR
+ENDFONT
+"#;
+
+    #[test]
+    fn it_scales_bitmaps_with_scale2x() {
+        // A diagonal line is widened into a smooth staircase
+        let pixels = scale2x(&[true, false, false, true], 2, 2);
+
+        assert_eq!(
+            pixels,
+            vec![
+                true, true, false, false, //
+                true, true, true, false, //
+                false, true, true, true, //
+                false, false, true, true, //
+            ]
+        );
+    }
+
+    #[test]
+    fn it_anti_aliases_glyphs() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+
+        let linear = font.apply_gamma(1.0);
+        let slash = linear.glyph('\\').unwrap();
+        assert_eq!(slash.bounding_box, (3, 3, 0, 0));
+        assert_eq!(slash.pixels, vec![207, 80, 0, 80, 223, 80, 0, 80, 207]);
+        // Only the corners of a solid block are rounded off
+        assert_eq!(
+            linear.glyph('#').unwrap().pixels,
+            vec![
+                175, 255, 255, 175, //
+                255, 255, 255, 255, //
+                255, 255, 255, 255, //
+                175, 255, 255, 175, //
+            ]
+        );
+
+        let corrected = font.apply_gamma(2.2);
+        let corrected_slash = corrected.glyph('\\').unwrap();
+        assert_eq!(
+            corrected_slash.pixels,
+            vec![232, 150, 0, 150, 240, 150, 0, 150, 232]
+        );
+    }
+}
@@ -0,0 +1,426 @@
+//! Compact binary representation of a font.
+//!
+//! All values are little endian. The data is split into three sections:
+//!
+//! * header: glyph count (`u32`), followed by the font bounding box as width (`u16`),
+//!   height (`u16`), x offset (`i16`) and y offset (`i16`)
+//! * index: one 18 byte entry per glyph containing the codepoint (`u32`, `0xffffffff` for
+//!   unencoded glyphs), bounding box width (`u16`), height (`u16`), x offset (`i16`),
+//!   y offset (`i16`), advance width (`u16`) and the offset of the bitmap in the bitmap section
+//!   (`u32`)
+//! * bitmaps: the bitmaps of all glyphs, with each row padded to a whole byte
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Write};
+
+use super::{BDFFont, Glyph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompactExportError {
+    /// The glyph count or the size of the bitmap section doesn't fit into a `u32`.
+    TooLarge,
+    /// The metrics of the named glyph, or of the font bounding box if the name is empty, don't
+    /// fit into the fields of the compact format.
+    MetricsOutOfRange(String),
+}
+
+impl fmt::Display for CompactExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompactExportError::TooLarge => write!(f, "font is too large"),
+            CompactExportError::MetricsOutOfRange(name) => {
+                write!(f, "metrics of glyph {:?} are out of range", name)
+            }
+        }
+    }
+}
+
+impl Error for CompactExportError {}
+
+/// Layout of a glyph as `(encoding, width, height, x_offset, y_offset, bitmap_offset,
+/// bitmap_len)`.
+pub type GlyphIndexEntry = (Option<char>, u32, u32, i32, i32, usize, usize);
+
+/// Position of a glyph bitmap and the glyph metrics in a lookup table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphDescriptor {
+    /// Offset of the bitmap in [`bitmaps_contiguous`].
+    ///
+    /// [`bitmaps_contiguous`]: struct.BDFFont.html#method.bitmaps_contiguous
+    pub bitmap_offset: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x_offset: i32,
+    pub y_offset: i32,
+    pub advance: u32,
+}
+
+pub(crate) struct CompactSections {
+    pub header: Vec<u8>,
+    pub index: Vec<u8>,
+    pub bitmaps: Vec<u8>,
+}
+
+impl BDFFont {
+    pub(crate) fn compact_sections(&self) -> Result<CompactSections, CompactExportError> {
+        let (width, height, x, y) = self
+            .metadata
+            .as_ref()
+            .map_or((0, 0, 0, 0), |metadata| metadata.bounding_box);
+        let count = u32::try_from(self.glyphs.len()).map_err(|_| CompactExportError::TooLarge)?;
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&count.to_le_bytes());
+        push_metrics(&mut header, (width, height, x, y), "")?;
+
+        let mut index = Vec::new();
+        let mut bitmaps = Vec::new();
+
+        for glyph in &self.glyphs {
+            let advance = u16::try_from(glyph.advance_width())
+                .map_err(|_| CompactExportError::MetricsOutOfRange(glyph.name.clone()))?;
+            let offset = u32::try_from(bitmaps.len()).map_err(|_| CompactExportError::TooLarge)?;
+
+            index.extend_from_slice(&(glyph.charcode as u32).to_le_bytes());
+            push_metrics(&mut index, glyph.bounding_box, &glyph.name)?;
+            index.extend_from_slice(&advance.to_le_bytes());
+            index.extend_from_slice(&offset.to_le_bytes());
+
+            bitmaps.extend(glyph.bitmap_bytes());
+        }
+
+        Ok(CompactSections {
+            header,
+            index,
+            bitmaps,
+        })
+    }
+
+    /// Returns the layout of every glyph.
+    ///
+    /// The bitmap offsets and lengths refer to the bitmaps of all glyphs concatenated in font
+    /// order, with each row padded to a whole byte, which is the layout of C font tables.
+    pub fn export_glyph_index(&self) -> Vec<GlyphIndexEntry> {
+        let mut bitmap_offset = 0;
+
+        self.glyphs
+            .iter()
+            .map(|glyph| {
+                let (width, height, x, y) = glyph.bounding_box;
+                let bitmap_len = glyph.stride() * height as usize;
+
+                let entry = (
+                    glyph.encoding(),
+                    width,
+                    height,
+                    x,
+                    y,
+                    bitmap_offset,
+                    bitmap_len,
+                );
+                bitmap_offset += bitmap_len;
+
+                entry
+            })
+            .collect()
+    }
+
+    /// Returns the encoded glyphs sorted by encoding, keeping only the first glyph of each
+    /// encoding.
+    pub(crate) fn lookup_glyphs(&self) -> Vec<(char, &Glyph)> {
+        let mut glyphs: Vec<_> = self
+            .glyphs
+            .iter()
+            .filter_map(|glyph| glyph.encoding().map(|c| (c, glyph)))
+            .collect();
+
+        // The sort is stable, which keeps the first glyph in front of its duplicates
+        glyphs.sort_by_key(|&(c, _)| c);
+        glyphs.dedup_by_key(|&mut (c, _)| c);
+
+        glyphs
+    }
+
+    /// Returns a table of all encoded glyphs sorted by codepoint, which can be searched with
+    /// `binary_search_by_key` or `partition_point`.
+    ///
+    /// The bitmap offsets refer to the output of [`bitmaps_contiguous`]. If multiple glyphs
+    /// share an encoding only the first one is included.
+    ///
+    /// [`bitmaps_contiguous`]: #method.bitmaps_contiguous
+    pub fn to_lookup_table(&self) -> Vec<(u32, GlyphDescriptor)> {
+        let mut bitmap_offset = 0;
+
+        self.lookup_glyphs()
+            .into_iter()
+            .map(|(c, glyph)| {
+                let (width, height, x_offset, y_offset) = glyph.bounding_box;
+                let descriptor = GlyphDescriptor {
+                    bitmap_offset,
+                    width,
+                    height,
+                    x_offset,
+                    y_offset,
+                    advance: glyph.advance_width(),
+                };
+                bitmap_offset += (glyph.stride() * height as usize) as u32;
+
+                (c as u32, descriptor)
+            })
+            .collect()
+    }
+
+    /// Returns the bitmaps of all glyphs in [`to_lookup_table`] in table order, with each row
+    /// padded to a whole byte.
+    ///
+    /// [`to_lookup_table`]: #method.to_lookup_table
+    pub fn bitmaps_contiguous(&self) -> Vec<u8> {
+        self.lookup_glyphs()
+            .into_iter()
+            .flat_map(|(_, glyph)| glyph.bitmap_bytes())
+            .collect()
+    }
+
+    /// Renders every glyph into a cell of `cell_width` x `cell_height` pixels and returns the
+    /// cells one after another.
+    ///
+    /// Each cell is stored row-major with each row padded to a whole byte, with the leftmost
+    /// pixel in the most significant bit. The bottom left corner of the cell is placed at the
+    /// offset of the font bounding box and pixels outside of the cell are clipped.
+    pub fn to_raw_1bpp(&self, cell_width: u32, cell_height: u32) -> Vec<u8> {
+        let (_, _, x, y) = self
+            .metadata
+            .as_ref()
+            .map_or((0, 0, 0, 0), |metadata| metadata.bounding_box);
+        let cell = (cell_width, cell_height, x, y);
+
+        self.glyphs
+            .iter()
+            .flat_map(|glyph| glyph.cell_bitmap(cell))
+            .collect()
+    }
+
+    /// Returns a hex dump of the compact binary representation of the font.
+    ///
+    /// Every section is labeled and dumped in the style of `xxd`, with offsets relative to the
+    /// start of the binary data. Fails if the font doesn't fit into the compact format.
+    pub fn to_hex_dump(&self) -> Result<String, CompactExportError> {
+        let sections = self.compact_sections()?;
+        let mut out = String::new();
+        let mut offset = 0;
+
+        for (label, data) in &[
+            ("header", sections.header),
+            ("index", sections.index),
+            ("bitmaps", sections.bitmaps),
+        ] {
+            writeln!(out, "# {} ({} bytes)", label, data.len()).unwrap();
+
+            for row in data.chunks(16) {
+                write!(out, "{:08x}: ", offset).unwrap();
+
+                for i in 0..16 {
+                    match row.get(i) {
+                        Some(byte) => write!(out, "{:02x}", byte).unwrap(),
+                        None => out.push_str("  "),
+                    }
+
+                    if i % 2 == 1 {
+                        out.push(' ');
+                    }
+                }
+
+                out.push(' ');
+                out.extend(row.iter().map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                }));
+                out.push('\n');
+
+                offset += row.len();
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Appends a bounding box as width (`u16`), height (`u16`), x offset (`i16`) and y offset
+/// (`i16`).
+fn push_metrics(
+    out: &mut Vec<u8>,
+    (width, height, x, y): (u32, u32, i32, i32),
+    name: &str,
+) -> Result<(), CompactExportError> {
+    let out_of_range = |_| CompactExportError::MetricsOutOfRange(name.to_string());
+
+    out.extend_from_slice(&u16::try_from(width).map_err(out_of_range)?.to_le_bytes());
+    out.extend_from_slice(&u16::try_from(height).map_err(out_of_range)?.to_le_bytes());
+    out.extend_from_slice(&i16::try_from(x).map_err(out_of_range)?.to_le_bytes());
+    out.extend_from_slice(&i16::try_from(y).map_err(out_of_range)?.to_le_bytes());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {BDFParser, Glyph};
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "binary"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 2 0 -1
+STARTCHAR A
+ENCODING 65
+DWIDTH 8 0
+BBX 8 2 0 0
+BITMAP
+41
+42
+ENDCHAR
+ENDFONT
+"#;
+
+    fn font() -> BDFFont {
+        BDFParser::from_str(FONT).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_builds_compact_sections() {
+        let sections = font().compact_sections().unwrap();
+
+        assert_eq!(
+            sections.header,
+            vec![1, 0, 0, 0, 8, 0, 2, 0, 0, 0, 0xff, 0xff]
+        );
+        assert_eq!(
+            sections.index,
+            vec![65, 0, 0, 0, 8, 0, 2, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(sections.bitmaps, vec![0x41, 0x42]);
+    }
+
+    #[test]
+    fn it_doesnt_truncate_large_bitmap_offsets() {
+        let mut font = font();
+        let mut glyph = font.glyphs[0].clone();
+        glyph.bounding_box = (256, 256, 0, 0);
+        glyph.bitmap_rows = 256;
+        font.glyphs = vec![glyph; 9];
+
+        let sections = font.compact_sections().unwrap();
+        let last_offset = &sections.index[sections.index.len() - 4..];
+
+        assert_eq!(last_offset, &(8u32 * 8192).to_le_bytes());
+        assert_eq!(sections.bitmaps.len(), 9 * 8192);
+    }
+
+    #[test]
+    fn it_rejects_metrics_that_dont_fit() {
+        let mut high = font();
+        high.glyphs[0].bounding_box.3 = 40_000;
+        assert_eq!(
+            high.to_hex_dump(),
+            Err(CompactExportError::MetricsOutOfRange("A".to_string()))
+        );
+
+        let mut wide = font();
+        wide.glyphs[0].device_width = Some((70_000, 0));
+        assert_eq!(
+            wide.compact_sections().err(),
+            Some(CompactExportError::MetricsOutOfRange("A".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_exports_raw_1bpp_cells() {
+        assert_eq!(
+            font().to_raw_1bpp(10, 3),
+            vec![
+                0x41, 0x00, //
+                0x42, 0x00, //
+                0x00, 0x00, // row below the baseline
+            ]
+        );
+        assert_eq!(font().to_raw_1bpp(4, 2), vec![0x40, 0x00]);
+    }
+
+    #[test]
+    fn it_dumps_hex() {
+        assert_eq!(
+            font().to_hex_dump().unwrap(),
+            [
+                "# header (12 bytes)",
+                "00000000: 0100 0000 0800 0200 0000 ffff            ............",
+                "# index (18 bytes)",
+                "0000000c: 4100 0000 0800 0200 0000 0000 0800 0000  A...............",
+                "0000001c: 0000                                     ..",
+                "# bitmaps (2 bytes)",
+                "0000001e: 4142                                     AB",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn it_exports_glyph_indexes() {
+        let mut font = font();
+        font.glyphs.push(Glyph {
+            name: "unencoded".to_string(),
+            charcode: -1,
+            device_width: None,
+            bounding_box: (12, 3, 1, -1),
+            bitmap: vec![0x1230_4560, 0x7890_0000],
+            bitmap_rows: 3,
+        });
+
+        assert_eq!(
+            font.export_glyph_index(),
+            vec![(Some('A'), 8, 2, 0, 0, 0, 2), (None, 12, 3, 1, -1, 2, 6),]
+        );
+    }
+
+    #[test]
+    fn it_builds_lookup_tables() {
+        let mut font = font();
+        let glyph = |name: &str, charcode, bitmap| Glyph {
+            name: name.to_string(),
+            charcode,
+            device_width: None,
+            bounding_box: (4, 1, 1, 0),
+            bitmap: vec![bitmap],
+            bitmap_rows: 1,
+        };
+        font.glyphs.insert(0, glyph("space", 32, 0x00));
+        font.glyphs.push(glyph("unencoded", -1, 0xf0));
+        font.glyphs.push(glyph("A.alt", 65, 0x10));
+
+        let descriptor = |bitmap_offset, width, height, x_offset, advance| GlyphDescriptor {
+            bitmap_offset,
+            width,
+            height,
+            x_offset,
+            y_offset: 0,
+            advance,
+        };
+
+        let table = font.to_lookup_table();
+        assert_eq!(
+            table,
+            vec![
+                (32, descriptor(0, 4, 1, 1, 4)),
+                (65, descriptor(1, 8, 2, 0, 8)),
+            ]
+        );
+        assert_eq!(font.bitmaps_contiguous(), vec![0x00, 0x41, 0x42]);
+
+        let index = table.partition_point(|&(c, _)| c < 65);
+        assert_eq!(table[index].0, 65);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use super::{BDFFont, BDFParser, BdfParseError};
+
+/// Caches parsed fonts by their canonical file path.
+///
+/// Fonts are parsed again if the modification time of the file has changed since it was last
+/// parsed.
+#[derive(Debug, Default)]
+pub struct BdfFontCache {
+    fonts: Mutex<HashMap<PathBuf, (SystemTime, Arc<BDFFont>)>>,
+}
+
+impl BdfFontCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the parsed font at `path`, reading and parsing it only if it isn't cached or the
+    /// file has been modified.
+    ///
+    /// The cache isn't locked while the font is parsed, so that fonts can be loaded in parallel.
+    /// If multiple threads parse the same file at the same time, the font of the first thread is
+    /// cached and returned to all of them.
+    pub fn get(&self, path: &Path) -> Result<Arc<BDFFont>, BdfParseError> {
+        let path = path.canonicalize()?;
+        let modified = fs::metadata(&path)?.modified()?;
+
+        if let Some((cached_modified, font)) = self.lock().get(&path) {
+            if *cached_modified == modified {
+                return Ok(Arc::clone(font));
+            }
+        }
+
+        let source = String::from_utf8_lossy(&fs::read(&path)?).into_owned();
+        let font = Arc::new(BDFParser::from_str(&source).parse_font()?);
+
+        let mut fonts = self.lock();
+        match fonts.get(&path) {
+            Some((cached_modified, cached)) if *cached_modified == modified => {
+                Ok(Arc::clone(cached))
+            }
+            _ => {
+                fonts.insert(path, (modified, Arc::clone(&font)));
+                Ok(font)
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, (SystemTime, Arc<BDFFont>)>> {
+        // A poisoned lock only means that another thread panicked while holding it, the
+        // cached fonts are still valid.
+        self.fonts.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "cache"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+CHARS 0
+ENDFONT
+"#;
+
+    #[test]
+    fn it_caches_fonts_until_they_are_modified() {
+        let path =
+            std::env::temp_dir().join(format!("bdf-parser-cache-{}.bdf", std::process::id()));
+        fs::write(&path, FONT).unwrap();
+
+        let cache = BdfFontCache::new();
+
+        let first = cache.get(&path).unwrap();
+        let second = cache.get(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        fs::write(&path, FONT.replace("\"cache\"", "\"modified\"")).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let third = cache.get(&path).unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+        assert_eq!(third.metadata.as_ref().unwrap().name, "\"modified\"");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_returns_the_same_font_to_concurrent_loads() {
+        let path = std::env::temp_dir().join(format!(
+            "bdf-parser-cache-concurrent-{}.bdf",
+            std::process::id()
+        ));
+        fs::write(&path, FONT).unwrap();
+
+        let cache = Arc::new(BdfFontCache::new());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let (cache, path) = (Arc::clone(&cache), path.clone());
+                std::thread::spawn(move || cache.get(&path).unwrap())
+            })
+            .collect();
+        let fonts: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+        let cached = cache.get(&path).unwrap();
+        assert!(fonts.iter().all(|font| Arc::ptr_eq(font, &cached)));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_reports_missing_files() {
+        let cache = BdfFontCache::new();
+
+        match cache.get(Path::new("does/not/exist.bdf")) {
+            Err(BdfParseError::Io(_)) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
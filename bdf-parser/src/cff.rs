@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use super::{BDFFont, Glyph};
+
+/// The predefined strings with SIDs 0 to 390, from appendix A of the CFF specification.
+#[rustfmt::skip]
+const STANDARD_STRINGS: [&str; 391] = [
+    ".notdef", "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand",
+    "quoteright", "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period",
+    "slash", "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "colon", "semicolon", "less", "equal", "greater", "question", "at", "A", "B", "C", "D", "E",
+    "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T", "U", "V", "W", "X",
+    "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", "quoteleft",
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "exclamdown",
+    "cent", "sterling", "fraction", "yen", "florin", "section", "currency", "quotesingle",
+    "quotedblleft", "guillemotleft", "guilsinglleft", "guilsinglright", "fi", "fl", "endash",
+    "dagger", "daggerdbl", "periodcentered", "paragraph", "bullet", "quotesinglbase",
+    "quotedblbase", "quotedblright", "guillemotright", "ellipsis", "perthousand", "questiondown",
+    "grave", "acute", "circumflex", "tilde", "macron", "breve", "dotaccent", "dieresis", "ring",
+    "cedilla", "hungarumlaut", "ogonek", "caron", "emdash", "AE", "ordfeminine", "Lslash", "Oslash",
+    "OE", "ordmasculine", "ae", "dotlessi", "lslash", "oslash", "oe", "germandbls", "onesuperior",
+    "logicalnot", "mu", "trademark", "Eth", "onehalf", "plusminus", "Thorn", "onequarter", "divide",
+    "brokenbar", "degree", "thorn", "threequarters", "twosuperior", "registered", "minus", "eth",
+    "multiply", "threesuperior", "copyright", "Aacute", "Acircumflex", "Adieresis", "Agrave",
+    "Aring", "Atilde", "Ccedilla", "Eacute", "Ecircumflex", "Edieresis", "Egrave", "Iacute",
+    "Icircumflex", "Idieresis", "Igrave", "Ntilde", "Oacute", "Ocircumflex", "Odieresis", "Ograve",
+    "Otilde", "Scaron", "Uacute", "Ucircumflex", "Udieresis", "Ugrave", "Yacute", "Ydieresis",
+    "Zcaron", "aacute", "acircumflex", "adieresis", "agrave", "aring", "atilde", "ccedilla",
+    "eacute", "ecircumflex", "edieresis", "egrave", "iacute", "icircumflex", "idieresis", "igrave",
+    "ntilde", "oacute", "ocircumflex", "odieresis", "ograve", "otilde", "scaron", "uacute",
+    "ucircumflex", "udieresis", "ugrave", "yacute", "ydieresis", "zcaron", "exclamsmall",
+    "Hungarumlautsmall", "dollaroldstyle", "dollarsuperior", "ampersandsmall", "Acutesmall",
+    "parenleftsuperior", "parenrightsuperior", "twodotenleader", "onedotenleader", "zerooldstyle",
+    "oneoldstyle", "twooldstyle", "threeoldstyle", "fouroldstyle", "fiveoldstyle", "sixoldstyle",
+    "sevenoldstyle", "eightoldstyle", "nineoldstyle", "commasuperior", "threequartersemdash",
+    "periodsuperior", "questionsmall", "asuperior", "bsuperior", "centsuperior", "dsuperior",
+    "esuperior", "isuperior", "lsuperior", "msuperior", "nsuperior", "osuperior", "rsuperior",
+    "ssuperior", "tsuperior", "ff", "ffi", "ffl", "parenleftinferior", "parenrightinferior",
+    "Circumflexsmall", "hyphensuperior", "Gravesmall", "Asmall", "Bsmall", "Csmall", "Dsmall",
+    "Esmall", "Fsmall", "Gsmall", "Hsmall", "Ismall", "Jsmall", "Ksmall", "Lsmall", "Msmall",
+    "Nsmall", "Osmall", "Psmall", "Qsmall", "Rsmall", "Ssmall", "Tsmall", "Usmall", "Vsmall",
+    "Wsmall", "Xsmall", "Ysmall", "Zsmall", "colonmonetary", "onefitted", "rupiah", "Tildesmall",
+    "exclamdownsmall", "centoldstyle", "Lslashsmall", "Scaronsmall", "Zcaronsmall", "Dieresissmall",
+    "Brevesmall", "Caronsmall", "Dotaccentsmall", "Macronsmall", "figuredash", "hypheninferior",
+    "Ogoneksmall", "Ringsmall", "Cedillasmall", "questiondownsmall", "oneeighth", "threeeighths",
+    "fiveeighths", "seveneighths", "onethird", "twothirds", "zerosuperior", "foursuperior",
+    "fivesuperior", "sixsuperior", "sevensuperior", "eightsuperior", "ninesuperior", "zeroinferior",
+    "oneinferior", "twoinferior", "threeinferior", "fourinferior", "fiveinferior", "sixinferior",
+    "seveninferior", "eightinferior", "nineinferior", "centinferior", "dollarinferior",
+    "periodinferior", "commainferior", "Agravesmall", "Aacutesmall", "Acircumflexsmall",
+    "Atildesmall", "Adieresissmall", "Aringsmall", "AEsmall", "Ccedillasmall", "Egravesmall",
+    "Eacutesmall", "Ecircumflexsmall", "Edieresissmall", "Igravesmall", "Iacutesmall",
+    "Icircumflexsmall", "Idieresissmall", "Ethsmall", "Ntildesmall", "Ogravesmall", "Oacutesmall",
+    "Ocircumflexsmall", "Otildesmall", "Odieresissmall", "OEsmall", "Oslashsmall", "Ugravesmall",
+    "Uacutesmall", "Ucircumflexsmall", "Udieresissmall", "Yacutesmall", "Thornsmall",
+    "Ydieresissmall", "001.000", "001.001", "001.002", "001.003", "Black", "Bold", "Book", "Light",
+    "Medium", "Regular", "Roman", "Semibold",
+];
+
+// Type 2 charstring operators
+const HLINETO: u8 = 6;
+const ENDCHAR: u8 = 14;
+const RMOVETO: u8 = 21;
+
+// DICT operators
+const ESCAPE: u8 = 12;
+const FONT_MATRIX: u8 = 7;
+const FONT_BBOX: u8 = 5;
+const CHARSET: u8 = 15;
+const CHAR_STRINGS: u8 = 17;
+const PRIVATE: u8 = 18;
+const DEFAULT_WIDTH_X: u8 = 20;
+const NOMINAL_WIDTH_X: u8 = 21;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CffExportError {
+    /// The font has no metadata to take the font bounding box from.
+    MissingMetadata,
+    /// CFF fonts are limited to 65535 glyphs, including `.notdef`.
+    TooManyGlyphs,
+}
+
+impl fmt::Display for CffExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CffExportError::MissingMetadata => write!(f, "font has no metadata"),
+            CffExportError::TooManyGlyphs => write!(f, "font has more than 65534 glyphs"),
+        }
+    }
+}
+
+impl Error for CffExportError {}
+
+impl BDFFont {
+    /// Converts the font into a bare CFF font program.
+    ///
+    /// Each glyph is traced into rectangular outlines, one per run of set pixels, which are
+    /// merged vertically where consecutive rows share the same run. Outlines use one unit per
+    /// pixel and the font matrix scales the height of the font bounding box to one em.
+    pub fn to_cff_subset(&self) -> Result<Vec<u8>, CffExportError> {
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or(CffExportError::MissingMetadata)?;
+
+        if self.glyphs.len() >= 0xffff {
+            return Err(CffExportError::TooManyGlyphs);
+        }
+
+        let (width, height, x, y) = metadata.bounding_box;
+        let scale = 1.0 / f64::from(height.max(1));
+
+        // Glyph names that aren't standard strings are stored in the String INDEX
+        let mut strings: Vec<Vec<u8>> = Vec::new();
+        let mut sids = HashMap::new();
+        let mut charset = vec![0];
+        for glyph in &self.glyphs {
+            let name = glyph.name.as_str();
+            let sid = match STANDARD_STRINGS.iter().position(|&s| s == name) {
+                Some(sid) => sid,
+                None => *sids.entry(name).or_insert_with(|| {
+                    strings.push(name.as_bytes().to_vec());
+                    STANDARD_STRINGS.len() + strings.len() - 1
+                }),
+            };
+            let sid = u16::try_from(sid).map_err(|_| CffExportError::TooManyGlyphs)?;
+            charset.extend_from_slice(&sid.to_be_bytes());
+        }
+
+        let name_index = index(&[postscript_name(&metadata.name).into_bytes()]);
+        let string_index = index(&strings);
+        let global_subr_index = index(&[]);
+
+        let mut charstrings = vec![charstring(None)];
+        charstrings.extend(self.glyphs.iter().map(|glyph| charstring(Some(glyph))));
+        let charstrings_index = index(&charstrings);
+
+        let mut private_dict = Vec::new();
+        dict_entry(&mut private_dict, &[0], DEFAULT_WIDTH_X);
+        dict_entry(&mut private_dict, &[0], NOMINAL_WIDTH_X);
+
+        // The top DICT contains offsets to the data that follows it, which are encoded with a
+        // fixed size to make the length of the top DICT independent of their values.
+        let top_dict = |charset_offset: usize, charstrings_offset: usize, private_offset: usize| {
+            let mut dict = Vec::new();
+            for &value in &[scale, 0.0, 0.0, scale, 0.0, 0.0] {
+                push_real(&mut dict, value);
+            }
+            dict.extend_from_slice(&[ESCAPE, FONT_MATRIX]);
+            dict_entry(
+                &mut dict,
+                &[x, y, x + width as i32, y + height as i32],
+                FONT_BBOX,
+            );
+            dict_offset(&mut dict, &[charset_offset], CHARSET);
+            dict_offset(&mut dict, &[charstrings_offset], CHAR_STRINGS);
+            dict_offset(&mut dict, &[private_dict.len(), private_offset], PRIVATE);
+            dict
+        };
+
+        let top_dict_index_len = index(&[top_dict(0, 0, 0)]).len();
+        let charset_offset = 4
+            + name_index.len()
+            + top_dict_index_len
+            + string_index.len()
+            + global_subr_index.len();
+        let charstrings_offset = charset_offset + charset.len();
+        let private_offset = charstrings_offset + charstrings_index.len();
+
+        let top_dict_index = index(&[top_dict(charset_offset, charstrings_offset, private_offset)]);
+
+        let mut out = vec![1, 0, 4, 4];
+        out.extend(name_index);
+        out.extend(top_dict_index);
+        out.extend(string_index);
+        out.extend(global_subr_index);
+        out.extend(charset);
+        out.extend(charstrings_index);
+        out.extend(private_dict);
+
+        Ok(out)
+    }
+}
+
+/// Strips all characters that aren't allowed in PostScript font names.
+fn postscript_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !"[](){}<>/%\"".contains(*c))
+        .take(63)
+        .collect();
+
+    if name.is_empty() {
+        "Untitled".to_string()
+    } else {
+        name
+    }
+}
+
+/// Traces the glyph into a Type 2 charstring, or returns an empty `.notdef` charstring for
+/// `None`.
+fn charstring(glyph: Option<&Glyph>) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    let glyph = match glyph {
+        Some(glyph) => glyph,
+        None => {
+            out.push(ENDCHAR);
+            return out;
+        }
+    };
+
+    let (width, height, x_offset, y_offset) = glyph.bounding_box;
+
+    // (left, right, top, bottom) in pixels, with the y axis pointing up
+    let mut rects: Vec<(u32, u32, i32, i32)> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+
+    for row in 0..height {
+        let y = y_offset + (height - 1 - row) as i32;
+        let mut still_open = Vec::new();
+
+        let mut x = 0;
+        while x < width {
+            if glyph.get_pixel(x, row) != Some(true) {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            while glyph.get_pixel(x, row) == Some(true) {
+                x += 1;
+            }
+
+            match open
+                .iter()
+                .find(|&&i| rects[i].0 == start && rects[i].1 == x)
+            {
+                Some(&i) => {
+                    rects[i].3 = y;
+                    still_open.push(i);
+                }
+                None => {
+                    still_open.push(rects.len());
+                    rects.push((start, x, y + 1, y));
+                }
+            }
+        }
+
+        open = still_open;
+    }
+
+    let mut width_arg = Some(glyph.advance_width() as i32);
+    let mut current = (0, 0);
+
+    for (left, right, top, bottom) in rects {
+        let left = x_offset + left as i32;
+        let right = x_offset + right as i32;
+
+        let mut args: Vec<i32> = width_arg.take().into_iter().collect();
+        args.extend_from_slice(&[left - current.0, bottom - current.1]);
+        charstring_op(&mut out, &args, RMOVETO);
+        charstring_op(
+            &mut out,
+            &[right - left, top - bottom, left - right],
+            HLINETO,
+        );
+
+        // Paths are closed implicitly, which leaves the current point in the top left corner.
+        current = (left, top);
+    }
+
+    let args: Vec<i32> = width_arg.into_iter().collect();
+    charstring_op(&mut out, &args, ENDCHAR);
+
+    out
+}
+
+fn charstring_op(out: &mut Vec<u8>, args: &[i32], operator: u8) {
+    for &value in args {
+        // Charstrings don't support 32 bit integers
+        push_int(out, value.clamp(-32768, 32767));
+    }
+
+    out.push(operator);
+}
+
+fn dict_entry(out: &mut Vec<u8>, args: &[i32], operator: u8) {
+    for &value in args {
+        push_int(out, value);
+    }
+
+    out.push(operator);
+}
+
+fn push_int(out: &mut Vec<u8>, value: i32) {
+    match value {
+        -107..=107 => out.push((value + 139) as u8),
+        108..=1131 => {
+            let value = value - 108;
+            out.extend_from_slice(&[(value >> 8) as u8 + 247, value as u8]);
+        }
+        -1131..=-108 => {
+            let value = -value - 108;
+            out.extend_from_slice(&[(value >> 8) as u8 + 251, value as u8]);
+        }
+        -32768..=32767 => {
+            out.push(28);
+            out.extend_from_slice(&(value as i16).to_be_bytes());
+        }
+        _ => {
+            out.push(29);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Writes a real number DICT operand in scientific notation.
+fn push_real(out: &mut Vec<u8>, value: f64) {
+    let text = format!("{:e}", value);
+
+    let mut nibbles = Vec::new();
+    let mut bytes = text.bytes().peekable();
+    while let Some(c) = bytes.next() {
+        nibbles.push(match c {
+            b'.' => 0xa,
+            b'e' if bytes.peek() == Some(&b'-') => {
+                bytes.next();
+                0xc
+            }
+            b'e' => 0xb,
+            b'-' => 0xe,
+            digit => digit - b'0',
+        });
+    }
+    nibbles.push(0xf);
+    if nibbles.len() % 2 == 1 {
+        nibbles.push(0xf);
+    }
+
+    out.push(30);
+    out.extend(nibbles.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+}
+
+/// Writes a DICT entry with all operands encoded as 5 byte integers.
+fn dict_offset(out: &mut Vec<u8>, args: &[usize], operator: u8) {
+    for &value in args {
+        out.push(29);
+        out.extend_from_slice(&(value as i32).to_be_bytes());
+    }
+
+    out.push(operator);
+}
+
+fn index(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = (items.len() as u16).to_be_bytes().to_vec();
+
+    if items.is_empty() {
+        return out;
+    }
+
+    let data_len: usize = items.iter().map(Vec::len).sum();
+    let offset_size = match data_len + 1 {
+        0..=0xff => 1,
+        0x100..=0xffff => 2,
+        0x1_0000..=0xff_ffff => 3,
+        _ => 4,
+    };
+
+    out.push(offset_size as u8);
+
+    let mut offset = 1;
+    for item in items.iter().map(Some).chain(Some(None)) {
+        out.extend_from_slice(&(offset as u32).to_be_bytes()[4 - offset_size..]);
+        if let Some(item) = item {
+            offset += item.len();
+        }
+    }
+
+    for item in items {
+        out.extend_from_slice(item);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    #[test]
+    fn it_encodes_numbers() {
+        let mut out = Vec::new();
+        charstring_op(&mut out, &[0, 107, 108, -1131, 2000], ENDCHAR);
+
+        assert_eq!(
+            out,
+            vec![139, 246, 247, 0, 254, 255, 28, 0x07, 0xd0, ENDCHAR]
+        );
+    }
+
+    #[test]
+    fn it_encodes_real_numbers() {
+        let mut out = Vec::new();
+        push_real(&mut out, 0.125);
+        push_real(&mut out, -2.5e-7);
+        push_real(&mut out, 0.0);
+
+        assert_eq!(
+            out,
+            vec![30, 0x1a, 0x25, 0xc1, 0xff, 30, 0xe2, 0xa5, 0xc7, 0xff, 30, 0x0b, 0x0f]
+        );
+    }
+
+    #[test]
+    fn it_encodes_indexes() {
+        assert_eq!(index(&[]), vec![0, 0]);
+        assert_eq!(
+            index(&[b"ab".to_vec(), b"c".to_vec()]),
+            vec![0, 2, 1, 1, 3, 4, b'a', b'b', b'c']
+        );
+    }
+
+    #[test]
+    fn it_traces_glyphs_into_rectangles() {
+        let glyph = Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: Some((4, 0)),
+            bounding_box: (3, 3, 0, 0),
+            bitmap: vec![0xe0a0a0],
+            bitmap_rows: 3,
+        };
+
+        // An outline of the top row and two columns below it
+        assert_eq!(
+            charstring(Some(&glyph)),
+            vec![
+                143, 139, 141, RMOVETO, 142, 140, 136, HLINETO, // top row
+                139, 136, RMOVETO, 140, 141, 138, HLINETO, // left column
+                141, 137, RMOVETO, 140, 141, 138, HLINETO, // right column
+                ENDCHAR,
+            ]
+        );
+    }
+
+    #[test]
+    fn it_exports_cff_fonts() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "cff test"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR A
+ENCODING 65
+DWIDTH 8 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let cff = font.to_cff_subset().unwrap();
+
+        // Header and name INDEX
+        assert_eq!(&cff[0..4], &[1, 0, 4, 4]);
+        assert_eq!(&cff[4..9], &[0, 1, 1, 1, 8]);
+        assert_eq!(&cff[9..16], b"cfftest");
+
+        // Private DICT at the end of the file
+        assert_eq!(&cff[cff.len() - 4..], &[139, 20, 139, 21]);
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn it_scales_tall_fonts_with_the_font_matrix() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "tall"
+SIZE 2000 75 75
+FONTBOUNDINGBOX 8 2000 0 0
+STARTCHAR A
+ENCODING 65
+DWIDTH 8 0
+BBX 1 1 0 1500
+BITMAP
+80
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let cff = font.to_cff_subset().unwrap();
+
+        // FontMatrix [0.0005 0 0 0.0005 0 0]
+        let mut matrix = Vec::new();
+        for &value in &[5e-4, 0.0, 0.0, 5e-4, 0.0, 0.0] {
+            push_real(&mut matrix, value);
+        }
+        matrix.extend_from_slice(&[ESCAPE, FONT_MATRIX]);
+        assert!(contains(&cff, &matrix));
+
+        let glyph = charstring(font.glyph('A'));
+        assert_eq!(&glyph[..6], &[147, 139, 28, 0x05, 0xdc, RMOVETO]);
+        assert!(contains(&cff, &glyph));
+    }
+
+    #[test]
+    fn it_uses_standard_strings_for_glyph_names() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "names"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR uni0100
+ENCODING 256
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR Semibold
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let cff = font.to_cff_subset().unwrap();
+
+        assert!(contains(&cff, &index(&[b"uni0100".to_vec()])));
+        assert!(contains(&cff, &[0, 0, 34, 1, 135, 1, 134]));
+    }
+}
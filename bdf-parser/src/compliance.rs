@@ -0,0 +1,310 @@
+use std::fmt;
+
+use super::BDFFont;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComplianceReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataWarning {
+    /// The version is neither 2.1 nor 2.2.
+    UnexpectedVersion(f32),
+    /// The font name is empty or `None`.
+    MissingName,
+    /// The horizontal or vertical resolution is 0.
+    ZeroResolution,
+    /// The font bounding box has no area.
+    EmptyBoundingBox,
+    /// The point size is 0.
+    ZeroPointSize,
+    /// `FONT_ASCENT + FONT_DESCENT` differs from the font bounding box height.
+    AscentDescentMismatch {
+        ascent: i32,
+        descent: i32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for MetadataWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetadataWarning::UnexpectedVersion(version) => {
+                write!(f, "unexpected BDF version {}", version)
+            }
+            MetadataWarning::MissingName => write!(f, "font has no name"),
+            MetadataWarning::ZeroResolution => write!(f, "resolution is 0"),
+            MetadataWarning::EmptyBoundingBox => write!(f, "font bounding box is empty"),
+            MetadataWarning::ZeroPointSize => write!(f, "point size is 0"),
+            MetadataWarning::AscentDescentMismatch {
+                ascent,
+                descent,
+                height,
+            } => write!(
+                f,
+                "ascent {} and descent {} don't add up to the bounding box height {}",
+                ascent, descent, height
+            ),
+        }
+    }
+}
+
+impl BDFFont {
+    /// Checks the font against the requirements of the BDF 2.1 specification.
+    ///
+    /// Violations of the specification are reported as errors, deviations that most readers
+    /// accept are reported as warnings.
+    pub fn verify_compliance(&self) -> ComplianceReport {
+        let mut report = ComplianceReport::default();
+
+        match self.metadata {
+            Some(ref metadata) => {
+                if metadata.version != 2.1 && metadata.version != 2.2 {
+                    report
+                        .warnings
+                        .push(format!("Unexpected BDF version {}", metadata.version));
+                }
+            }
+            None => report.errors.push(
+                "Missing or misordered STARTFONT, FONT, SIZE and FONTBOUNDINGBOX header"
+                    .to_string(),
+            ),
+        }
+
+        match self.chars {
+            Some(chars) if chars as usize != self.glyphs.len() => report.errors.push(format!(
+                "CHARS declares {} glyphs, but the font contains {}",
+                chars,
+                self.glyphs.len()
+            )),
+            Some(_) => {}
+            None => report.errors.push("Missing CHARS".to_string()),
+        }
+
+        for glyph in &self.glyphs {
+            if glyph.name.trim().is_empty() {
+                report
+                    .errors
+                    .push(format!("Glyph {} has no STARTCHAR name", glyph.charcode));
+            }
+
+            if glyph.bitmap_rows != glyph.bounding_box.1 {
+                report.errors.push(format!(
+                    "BITMAP of glyph {:?} has {} rows, but the BBX height is {}",
+                    glyph.name, glyph.bitmap_rows, glyph.bounding_box.1
+                ));
+            }
+
+            if !glyph.bounding_box_contains_all_set_pixels() {
+                report.errors.push(format!(
+                    "BITMAP of glyph {:?} has set pixels outside of the BBX width",
+                    glyph.name
+                ));
+            }
+        }
+
+        if !self.endfont {
+            report.warnings.push("Missing ENDFONT".to_string());
+        }
+
+        report
+    }
+
+    /// Checks the metadata for values that are valid, but usually caused by buggy export tools.
+    ///
+    /// Fonts without metadata don't produce any warnings, see [`verify_compliance`] instead.
+    ///
+    /// [`verify_compliance`]: #method.verify_compliance
+    pub fn warn_on_suspicious_metadata(&self) -> Vec<MetadataWarning> {
+        let mut warnings = Vec::new();
+
+        let metadata = match self.metadata {
+            Some(ref metadata) => metadata,
+            None => return warnings,
+        };
+
+        if metadata.version != 2.1 && metadata.version != 2.2 {
+            warnings.push(MetadataWarning::UnexpectedVersion(metadata.version));
+        }
+
+        let name = metadata.name.trim().trim_matches('"').trim();
+        if name.is_empty() || name == "None" {
+            warnings.push(MetadataWarning::MissingName);
+        }
+
+        let (point_size, x_resolution, y_resolution) = metadata.size;
+        if x_resolution == 0 || y_resolution == 0 {
+            warnings.push(MetadataWarning::ZeroResolution);
+        }
+
+        let (width, height, _, _) = metadata.bounding_box;
+        if width == 0 || height == 0 {
+            warnings.push(MetadataWarning::EmptyBoundingBox);
+        }
+
+        if point_size == 0 {
+            warnings.push(MetadataWarning::ZeroPointSize);
+        }
+
+        if let (Some(ascent), Some(descent)) = (
+            self.int_property("FONT_ASCENT"),
+            self.int_property("FONT_DESCENT"),
+        ) {
+            if ascent + descent != height as i32 {
+                warnings.push(MetadataWarning::AscentDescentMismatch {
+                    ascent,
+                    descent,
+                    height,
+                });
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    #[test]
+    fn it_accepts_compliant_fonts() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "compliant"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 2 0 0
+CHARS 1
+STARTCHAR A
+ENCODING 65
+BBX 8 2 0 0
+BITMAP
+ff
+81
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(font.verify_compliance(), ComplianceReport::default());
+    }
+
+    #[test]
+    fn it_reports_compliance_problems() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.2
+FONT "broken"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 2 0 0
+CHARS 5
+STARTCHAR A
+ENCODING 65
+BBX 8 8 0 0
+BITMAP
+ff
+81
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+BBX 4 1 0 0
+BITMAP
+f8
+ENDCHAR
+STARTCHAR C
+ENCODING 67
+BBX 8 3 0 0
+BITMAP
+ff
+81
+ENDCHAR
+STARTCHAR D
+ENCODING 68
+BBX 8 2 0 0
+BITMAP
+ff
+81
+42
+ENDCHAR
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.verify_compliance(),
+            ComplianceReport {
+                errors: vec![
+                    "CHARS declares 5 glyphs, but the font contains 4".to_string(),
+                    "BITMAP of glyph \"A\" has 2 rows, but the BBX height is 8".to_string(),
+                    "BITMAP of glyph \"B\" has set pixels outside of the BBX width".to_string(),
+                    "BITMAP of glyph \"C\" has 2 rows, but the BBX height is 3".to_string(),
+                    "BITMAP of glyph \"D\" has 3 rows, but the BBX height is 2".to_string(),
+                ],
+                warnings: vec!["Missing ENDFONT".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn it_accepts_plausible_metadata() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.2
+FONT "plausible"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 10 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 8
+FONT_DESCENT 2
+ENDPROPERTIES
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(font.warn_on_suspicious_metadata(), vec![]);
+    }
+
+    #[test]
+    fn it_warns_on_suspicious_metadata() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.0
+FONT "None"
+SIZE 0 75 0
+FONTBOUNDINGBOX 0 10 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 8
+FONT_DESCENT 1
+ENDPROPERTIES
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.warn_on_suspicious_metadata(),
+            vec![
+                MetadataWarning::UnexpectedVersion(2.0),
+                MetadataWarning::MissingName,
+                MetadataWarning::ZeroResolution,
+                MetadataWarning::EmptyBoundingBox,
+                MetadataWarning::ZeroPointSize,
+                MetadataWarning::AscentDescentMismatch {
+                    ascent: 8,
+                    descent: 1,
+                    height: 10,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+
+use super::BDFFont;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EpdExportError {
+    /// The font has no metadata to take the cell size from.
+    MissingMetadata,
+    /// The first character is after the last character.
+    InvalidRange,
+    /// The character can't be stored in a single byte.
+    CharOutOfRange(char),
+    /// The font bounding box doesn't fit into a single byte.
+    CellTooLarge,
+}
+
+impl fmt::Display for EpdExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EpdExportError::MissingMetadata => write!(f, "font has no metadata"),
+            EpdExportError::InvalidRange => write!(f, "first char is after last char"),
+            EpdExportError::CharOutOfRange(c) => {
+                write!(f, "char {:?} can't be stored in a single byte", c)
+            }
+            EpdExportError::CellTooLarge => write!(f, "font bounding box is larger than 255"),
+        }
+    }
+}
+
+impl Error for EpdExportError {}
+
+impl BDFFont {
+    /// Converts the font into the format used by many e-paper display drivers.
+    ///
+    /// The output starts with a 4 byte header containing the cell width, cell height, first char
+    /// and last char, followed by one bitmap per char in the range. Each bitmap covers the whole
+    /// font bounding box, stored row-major with each row padded to a whole byte. Chars without
+    /// a glyph are stored as an empty cell.
+    pub fn to_epd_font(
+        &self,
+        first_char: char,
+        last_char: char,
+    ) -> Result<Vec<u8>, EpdExportError> {
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or(EpdExportError::MissingMetadata)?;
+
+        if first_char > last_char {
+            return Err(EpdExportError::InvalidRange);
+        }
+
+        for &c in &[first_char, last_char] {
+            if c as u32 > 0xff {
+                return Err(EpdExportError::CharOutOfRange(c));
+            }
+        }
+
+        let cell = metadata.bounding_box;
+        let (width, height, _, _) = cell;
+
+        if width > 0xff || height > 0xff {
+            return Err(EpdExportError::CellTooLarge);
+        }
+
+        let mut out = vec![width as u8, height as u8, first_char as u8, last_char as u8];
+        let cell_len = (width as usize).div_ceil(8) * height as usize;
+
+        for c in first_char..=last_char {
+            match self.glyph(c) {
+                Some(glyph) => out.extend(glyph.cell_bitmap(cell)),
+                None => out.extend(vec![0; cell_len]),
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "epd"
+SIZE 4 75 75
+FONTBOUNDINGBOX 4 4 0 -1
+STARTCHAR A
+ENCODING 65
+DWIDTH 4 0
+BBX 3 3 0 0
+BITMAP
+40
+A0
+E0
+ENDCHAR
+STARTCHAR C
+ENCODING 67
+DWIDTH 4 0
+BBX 2 1 1 -1
+BITMAP
+C0
+ENDCHAR
+ENDFONT
+"#;
+
+    fn font() -> BDFFont {
+        BDFParser::from_str(FONT).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_exports_epd_fonts() {
+        assert_eq!(
+            font().to_epd_font('A', 'C'),
+            Ok(vec![
+                4, 4, 65, 67, // header
+                0x40, 0xa0, 0xe0, 0x00, // A
+                0x00, 0x00, 0x00, 0x00, // B (missing)
+                0x00, 0x00, 0x00, 0x60, // C
+            ])
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_ranges() {
+        assert_eq!(
+            font().to_epd_font('C', 'A'),
+            Err(EpdExportError::InvalidRange)
+        );
+        assert_eq!(
+            font().to_epd_font('A', '\u{100}'),
+            Err(EpdExportError::CharOutOfRange('\u{100}'))
+        );
+    }
+}
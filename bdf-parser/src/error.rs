@@ -0,0 +1,249 @@
+use nom::types::CompleteByteSlice;
+use nom::{Context, Err, ErrorKind};
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum BdfParseError {
+    /// The font file couldn't be read.
+    Io(io::Error),
+    /// The source couldn't be parsed.
+    Parse {
+        /// Byte offset at which parsing failed.
+        offset: usize,
+        message: String,
+    },
+}
+
+impl BdfParseError {
+    /// Converts a nom error into a parse error with the offset relative to `source`.
+    pub(crate) fn from_nom(source: &str, error: Err<CompleteByteSlice>) -> Self {
+        match error {
+            Err::Incomplete(_) => BdfParseError::Parse {
+                offset: source.len(),
+                message: "Unexpected end of input".to_string(),
+            },
+            Err::Error(Context::Code(rest, kind)) | Err::Failure(Context::Code(rest, kind)) => {
+                let offset = source.len() - rest.len();
+
+                BdfParseError::Parse {
+                    offset,
+                    message: describe(source, offset, &kind),
+                }
+            }
+        }
+    }
+
+    /// Creates an error for input that is left over after parsing ends at `offset`.
+    pub(crate) fn unexpected_data(source: &str, offset: usize) -> Self {
+        BdfParseError::Parse {
+            offset,
+            message: describe(source, offset, &ErrorKind::Alt),
+        }
+    }
+}
+
+/// Keywords that start a line in a BDF file.
+const KEYWORDS: &[&str] = &[
+    "STARTFONT",
+    "COMMENT",
+    "CONTENTVERSION",
+    "FONT",
+    "SIZE",
+    "FONTBOUNDINGBOX",
+    "METRICSSET",
+    "STARTPROPERTIES",
+    "ENDPROPERTIES",
+    "CHARS",
+    "STARTCHAR",
+    "ENCODING",
+    "SWIDTH",
+    "DWIDTH",
+    "SWIDTH1",
+    "DWIDTH1",
+    "VVECTOR",
+    "BBX",
+    "BITMAP",
+    "ENDCHAR",
+    "ENDFONT",
+];
+
+/// Returns the keyword at the start of the line, if the line starts with a keyword.
+fn line_keyword(line: &str) -> Option<&str> {
+    let word = line.split_whitespace().next()?;
+
+    KEYWORDS.iter().find(|&&keyword| keyword == word).cloned()
+}
+
+/// Describes what the parser expected at `offset`, using the line and the section of the file
+/// that contain the offset as context.
+fn describe(source: &str, offset: usize, kind: &ErrorKind) -> String {
+    let expected = match kind {
+        ErrorKind::Tag => "Expected keyword",
+        ErrorKind::Digit => "Expected integer",
+        ErrorKind::HexDigit => "Expected hex digits",
+        ErrorKind::Alpha | ErrorKind::AlphaNumeric => "Expected name",
+        ErrorKind::Space | ErrorKind::MultiSpace => "Expected whitespace",
+        ErrorKind::Char | ErrorKind::OneOf | ErrorKind::NoneOf => "Unexpected character",
+        ErrorKind::TakeUntil | ErrorKind::TakeUntilAndConsume | ErrorKind::TakeUntilAndConsume1 => {
+            "Missing terminator"
+        }
+        ErrorKind::ParseTo | ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => {
+            "Invalid value"
+        }
+        ErrorKind::TooLarge => "Value too large",
+        ErrorKind::Eof => "Expected end of input",
+        ErrorKind::Complete => "Unexpected end of input",
+        ErrorKind::Alt | ErrorKind::Switch | ErrorKind::Permutation | ErrorKind::Not => {
+            "Unexpected data"
+        }
+        kind => kind.description(),
+    };
+
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index);
+
+    if let Some(keyword) = line_keyword(&source[line_start..line_end]) {
+        return format!("{} in {} line", expected, keyword);
+    }
+
+    // Lines without a keyword are bitmap rows or properties
+    let section = source[..line_start]
+        .lines()
+        .rev()
+        .filter_map(line_keyword)
+        .next();
+    let missing_terminator = matches!(
+        kind,
+        ErrorKind::Tag
+            | ErrorKind::TakeUntil
+            | ErrorKind::TakeUntilAndConsume
+            | ErrorKind::TakeUntilAndConsume1
+    );
+    match (section, kind) {
+        (Some("BITMAP"), _) if missing_terminator => "Expected ENDCHAR keyword".to_string(),
+        (Some("BITMAP"), _) => format!("{} in bitmap", expected),
+        (Some("STARTPROPERTIES"), _) if missing_terminator => {
+            "Expected ENDPROPERTIES keyword".to_string()
+        }
+        (Some("STARTPROPERTIES"), _) => format!("{} in properties", expected),
+        _ => expected.to_string(),
+    }
+}
+
+impl fmt::Display for BdfParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BdfParseError::Io(error) => write!(f, "couldn't read font: {}", error),
+            BdfParseError::Parse { offset, message } => {
+                write!(f, "{} at byte offset {}", message, offset)
+            }
+        }
+    }
+}
+
+impl Error for BdfParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BdfParseError::Io(error) => Some(error),
+            BdfParseError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for BdfParseError {
+    fn from(error: io::Error) -> Self {
+        BdfParseError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use BDFParser;
+
+    const HEADER: &str = "STARTFONT 2.1
+FONT \"test\"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+";
+
+    fn error(source: &str) -> String {
+        BDFParser::from_str(source)
+            .parse_font()
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn it_describes_errors_in_header_lines() {
+        let source = HEADER.replace("SIZE 8", "SIZE x") + "ENDFONT\n";
+
+        assert_eq!(
+            error(&source),
+            "Expected integer in SIZE line at byte offset 31"
+        );
+    }
+
+    #[test]
+    fn it_describes_errors_in_glyph_lines() {
+        let source = HEADER.to_string()
+            + "STARTCHAR A
+ENCODING x
+BBX 8 1 0 0
+BITMAP
+ff
+ENDCHAR
+ENDFONT
+";
+
+        assert_eq!(
+            error(&source),
+            "Expected integer in ENCODING line at byte offset 84"
+        );
+    }
+
+    #[test]
+    fn it_describes_errors_in_bitmaps() {
+        let source = HEADER.to_string()
+            + "STARTCHAR A
+ENCODING 65
+BBX 8 1 0 0
+BITMAP
+ff
+ENDFONT
+";
+
+        assert_eq!(
+            error(&source),
+            "Expected ENDCHAR keyword at byte offset 106"
+        );
+    }
+
+    #[test]
+    fn it_describes_errors_in_properties() {
+        let source = HEADER.to_string()
+            + "STARTPROPERTIES 1
+COPYRIGHT \"none\"
+STARTCHAR A
+";
+
+        assert_eq!(
+            error(&source),
+            "Expected ENDPROPERTIES keyword at byte offset 81"
+        );
+    }
+
+    #[test]
+    fn it_describes_unexpected_data() {
+        let source = HEADER.to_string() + "BOGUS\nENDFONT\n";
+
+        assert_eq!(error(&source), "Unexpected data at byte offset 63");
+    }
+}
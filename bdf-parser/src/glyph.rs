@@ -1,3 +1,7 @@
+#[cfg(feature = "embedded-graphics")]
+use embedded_graphics::pixelcolor::PixelColor;
+#[cfg(feature = "embedded-graphics")]
+use embedded_graphics::prelude::{Pixel, Point};
 use nom::types::CompleteByteSlice;
 use nom::*;
 
@@ -10,8 +14,289 @@ type Vec2 = (u32, u32);
 pub struct Glyph {
     pub name: String,
     pub charcode: i32,
+    pub device_width: Option<Vec2>,
     pub bounding_box: BoundingBox,
     pub bitmap: Vec<u32>,
+    /// Number of rows in the bitmap, which only differs from the bounding box height in
+    /// malformed fonts.
+    pub bitmap_rows: u32,
+}
+
+impl Glyph {
+    /// Returns the encoding as a `char`, or `None` for unencoded glyphs.
+    pub fn encoding(&self) -> Option<char> {
+        if self.charcode < 0 {
+            None
+        } else {
+            ::std::char::from_u32(self.charcode as u32)
+        }
+    }
+
+    /// Number of bytes used to store each bitmap row.
+    pub fn stride(&self) -> usize {
+        (self.bounding_box.0 as usize).div_ceil(8)
+    }
+
+    /// Horizontal advance in pixels, falling back to the bounding box width if the glyph has no
+    /// `DWIDTH`.
+    pub fn advance_width(&self) -> u32 {
+        self.device_width
+            .map_or(self.bounding_box.0, |(width, _)| width)
+    }
+
+    /// Returns the bitmap as bytes, one row after another, each row padded to a whole byte.
+    pub fn bitmap_bytes(&self) -> Vec<u8> {
+        let len = self.stride() * self.bounding_box.1 as usize;
+
+        (0..len).map(|index| self.bitmap_byte(index)).collect()
+    }
+
+    /// Returns the state of the pixel at `(x, y)`, relative to the top left corner of the
+    /// bounding box, or `None` if the coordinates are outside the bounding box.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<bool> {
+        let (width, height, _, _) = self.bounding_box;
+
+        if x >= width || y >= height {
+            return None;
+        }
+
+        let byte = self.bitmap_byte(y as usize * self.stride() + x as usize / 8);
+
+        Some(byte & (0x80 >> (x % 8)) != 0)
+    }
+
+    /// Returns an iterator over all pixels inside the bounding box.
+    ///
+    /// Each pixel is returned as `((x, y), on)` with the position relative to the glyph origin,
+    /// using the BDF coordinate system with the y axis pointing up. Pixels below the baseline
+    /// have negative y coordinates.
+    pub fn pixels(&self) -> GlyphPixels<'_> {
+        GlyphPixels {
+            glyph: self,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    /// Converts the glyph into `embedded-graphics` pixels with the origin on the baseline.
+    ///
+    /// Unlike [`pixels`](#method.pixels) the y axis points down, to match the display
+    /// coordinates used by `DrawTarget`.
+    #[cfg(feature = "embedded-graphics")]
+    pub fn render_to<'a, C: PixelColor + 'a>(
+        &'a self,
+        color_on: C,
+        color_off: C,
+    ) -> impl Iterator<Item = Pixel<C>> + 'a {
+        self.pixels().map(move |((x, y), on)| {
+            Pixel(Point::new(x, -y), if on { color_on } else { color_off })
+        })
+    }
+
+    /// Returns the number of set pixels inside the bounding box.
+    pub fn count_set_pixels(&self) -> u32 {
+        let (width, height, _, _) = self.bounding_box;
+
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.get_pixel(x, y) == Some(true))
+            .count() as u32
+    }
+
+    /// Returns the fraction of the bounding box area that is covered by set pixels, or `0.0`
+    /// for an empty bounding box.
+    pub fn pixel_density(&self) -> f32 {
+        let (width, height, _, _) = self.bounding_box;
+        let area = width * height;
+
+        if area == 0 {
+            0.0
+        } else {
+            self.count_set_pixels() as f32 / area as f32
+        }
+    }
+
+    /// Returns `false` if any of the padding bits at the end of each bitmap row is set.
+    pub fn bounding_box_contains_all_set_pixels(&self) -> bool {
+        let (width, height, _, _) = self.bounding_box;
+        let stride = self.stride();
+
+        if width % 8 == 0 {
+            return true;
+        }
+
+        let padding_mask = 0xff >> (width % 8);
+
+        (0..height as usize).all(|y| self.bitmap_byte(y * stride + stride - 1) & padding_mask == 0)
+    }
+
+    /// Renders the glyph into a cell described by a font bounding box. The returned rows are
+    /// padded to a whole byte and pixels outside the cell are clipped.
+    pub(crate) fn cell_bitmap(&self, cell: BoundingBox) -> Vec<u8> {
+        let (cell_width, cell_height, cell_x, cell_y) = cell;
+        let (width, height, x_offset, y_offset) = self.bounding_box;
+
+        let stride = (cell_width as usize).div_ceil(8);
+        let mut bytes = vec![0; stride * cell_height as usize];
+
+        let left = x_offset - cell_x;
+        let top = (cell_y + cell_height as i32) - (y_offset + height as i32);
+
+        for y in 0..height {
+            for x in 0..width {
+                if self.get_pixel(x, y) != Some(true) {
+                    continue;
+                }
+
+                let cx = left + x as i32;
+                let cy = top + y as i32;
+
+                if cx >= 0 && cy >= 0 && cx < cell_width as i32 && cy < cell_height as i32 {
+                    bytes[cy as usize * stride + cx as usize / 8] |= 0x80 >> (cx % 8);
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Returns the smallest rectangle `(left, top, width, height)` that contains all set pixels,
+    /// or `None` if no pixel is set.
+    pub(crate) fn ink_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let (width, height, _, _) = self.bounding_box;
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+
+        for y in 0..height {
+            for x in 0..width {
+                if self.get_pixel(x, y) != Some(true) {
+                    continue;
+                }
+
+                bounds = Some(match bounds {
+                    Some((left, top, right, bottom)) => {
+                        (left.min(x), top.min(y), right.max(x + 1), bottom.max(y + 1))
+                    }
+                    None => (x, y, x + 1, y + 1),
+                });
+            }
+        }
+
+        bounds.map(|(left, top, right, bottom)| (left, top, right - left, bottom - top))
+    }
+
+    /// Crops the bitmap to the given rectangle, keeping the remaining pixels at the same
+    /// position relative to the glyph origin.
+    pub(crate) fn crop(&mut self, left: u32, top: u32, width: u32, height: u32) {
+        let bitmap = pack_pixels(width, height, |x, y| {
+            self.get_pixel(left + x, top + y) == Some(true)
+        });
+
+        let (_, old_height, x_offset, y_offset) = self.bounding_box;
+
+        self.bounding_box = (
+            width,
+            height,
+            x_offset + left as i32,
+            y_offset + (old_height - top - height) as i32,
+        );
+        self.bitmap = bitmap;
+        self.bitmap_rows = height;
+    }
+
+    fn bitmap_byte(&self, index: usize) -> u8 {
+        let len = self.stride() * self.bitmap_rows as usize;
+        let words = self.bitmap.len();
+        let word = index / 4;
+
+        if index >= len || word >= words {
+            return 0;
+        }
+
+        // The parser packs 8 hex digits into each word, which leaves the last word right
+        // aligned if the bitmap length isn't a multiple of 4 bytes.
+        let word_bytes = if word + 1 == words {
+            (len - word * 4).min(4)
+        } else {
+            4
+        };
+
+        let shift = (word_bytes - 1 - index % 4) * 8;
+
+        (self.bitmap[word] >> shift) as u8
+    }
+}
+
+/// Iterator over the pixels of a glyph, see [`Glyph::pixels`](struct.Glyph.html#method.pixels).
+///
+/// Each item is `((x, y), on)`. The position is relative to the glyph origin on the baseline and
+/// uses the BDF coordinate system, in which the y axis points up. This is the opposite of the
+/// display coordinates used by `embedded-graphics`, so the y coordinate must be negated to get a
+/// `Point`, which is what [`Glyph::render_to`](struct.Glyph.html#method.render_to) does.
+///
+/// Pixels are returned row by row, starting with the top row of the bounding box and going from
+/// left to right in each row.
+#[derive(Debug, Clone)]
+pub struct GlyphPixels<'a> {
+    glyph: &'a Glyph,
+    x: u32,
+    y: u32,
+}
+
+impl<'a> Iterator for GlyphPixels<'a> {
+    type Item = ((i32, i32), bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (width, height, x_offset, y_offset) = self.glyph.bounding_box;
+
+        if width == 0 || self.y >= height {
+            return None;
+        }
+
+        let on = self.glyph.get_pixel(self.x, self.y) == Some(true);
+        let point = (
+            x_offset + self.x as i32,
+            y_offset + (height - 1 - self.y) as i32,
+        );
+
+        self.x += 1;
+        if self.x == width {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        Some((point, on))
+    }
+}
+
+/// Packs bitmap bytes into words the same way the parser does.
+pub(crate) fn pack_bitmap(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |word, &byte| (word << 8) | u32::from(byte))
+        })
+        .collect()
+}
+
+/// Creates a packed bitmap of the given size with the pixels set by `f(x, y)`.
+pub(crate) fn pack_pixels<F>(width: u32, height: u32, f: F) -> Vec<u32>
+where
+    F: Fn(u32, u32) -> bool,
+{
+    let stride = (width as usize).div_ceil(8);
+    let mut bytes = vec![0; stride * height as usize];
+
+    for y in 0..height {
+        for x in 0..width {
+            if f(x, y) {
+                bytes[y as usize * stride + x as usize / 8] |= 0x80 >> (x % 8);
+            }
+        }
+    }
+
+    pack_bitmap(&bytes)
 }
 
 named!(
@@ -48,14 +333,26 @@ named!(
     ))
 );
 
+/// Returns the number of lines in the bitmap data that contain hex digits.
+fn count_bitmap_rows(data: &[u8]) -> u32 {
+    data.split(|&byte| byte == b'\n')
+        .filter(|line| line.iter().any(|&byte| is_hex_digit(byte)))
+        .count() as u32
+}
+
+named!(
+    glyph_bitmap_data<CompleteByteSlice, CompleteByteSlice>,
+    ws!(delimited!(
+        tag!("BITMAP"),
+        take_until!("ENDCHAR"),
+        tag!("ENDCHAR")
+    ))
+);
+
 named!(
     glyph_bitmap<CompleteByteSlice, Vec<u32>>,
     map!(
-        ws!(delimited!(
-            tag!("BITMAP"),
-            take_until!("ENDCHAR"),
-            tag!("ENDCHAR")
-        )),
+        glyph_bitmap_data,
         |res| {
             res.to_vec()
                 .iter()
@@ -82,11 +379,14 @@ named!(
     ws!(preceded!(
         tag!("STARTCHAR"),
         do_parse!(
-            name: glyph_name >> charcode: glyph_charcode >> opt!(glyph_swidth) >> opt!(glyph_dwidth)
-                >> bounding_box: glyph_bounding_box >> bitmap: glyph_bitmap >> ({
+            name: glyph_name >> charcode: glyph_charcode >> opt!(glyph_swidth) >> device_width: opt!(glyph_dwidth)
+                >> bounding_box: glyph_bounding_box >> bitmap_rows: peek!(map!(glyph_bitmap_data, |data| count_bitmap_rows(&data)))
+                >> bitmap: glyph_bitmap >> ({
                 Glyph {
                     bitmap,
+                    bitmap_rows,
                     bounding_box,
+                    device_width,
                     charcode,
                     name,
                 }
@@ -137,6 +437,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_unpacks_bitmap_bytes() {
+        let glyph = Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: None,
+            bounding_box: (12, 3, 0, 0),
+            bitmap: vec![0x8010ff00, 0xa5f0],
+            bitmap_rows: 3,
+        };
+
+        assert_eq!(glyph.stride(), 2);
+        assert_eq!(
+            glyph.bitmap_bytes(),
+            vec![0x80, 0x10, 0xff, 0x00, 0xa5, 0xf0]
+        );
+    }
+
+    #[test]
+    fn it_counts_set_pixels() {
+        let mut glyph = Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: None,
+            bounding_box: (3, 2, 0, 0),
+            bitmap: vec![0xe1a0],
+            bitmap_rows: 2,
+        };
+
+        // The padding bit in the first row isn't counted
+        assert_eq!(glyph.count_set_pixels(), 5);
+        assert_eq!(glyph.pixel_density(), 5.0 / 6.0);
+
+        glyph.bounding_box = (0, 0, 0, 0);
+        glyph.bitmap = vec![];
+        glyph.bitmap_rows = 0;
+        assert_eq!(glyph.count_set_pixels(), 0);
+        assert_eq!(glyph.pixel_density(), 0.0);
+    }
+
+    fn pixel_glyph(width: u32, height: u32, y_offset: i32, rows: &[u8]) -> Glyph {
+        Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: None,
+            bounding_box: (width, height, 0, y_offset),
+            bitmap: pack_bitmap(rows),
+            bitmap_rows: height,
+        }
+    }
+
+    fn lit_pixels(glyph: &Glyph) -> Vec<(i32, i32)> {
+        glyph
+            .pixels()
+            .filter(|(_, on)| *on)
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    #[test]
+    fn it_iterates_pixels_of_narrow_glyphs() {
+        let glyph = pixel_glyph(1, 2, 0, &[0x80, 0x7f]);
+        assert_eq!(glyph.pixels().collect::<Vec<_>>(), vec![((0, 1), true), ((0, 0), false)]);
+
+        let glyph = pixel_glyph(7, 1, 0, &[0x83]);
+        assert_eq!(glyph.pixels().count(), 7);
+        assert_eq!(lit_pixels(&glyph), vec![(0, 0), (6, 0)]);
+    }
+
+    #[test]
+    fn it_iterates_pixels_of_byte_aligned_glyphs() {
+        let glyph = pixel_glyph(8, 2, 0, &[0x81, 0x00]);
+        assert_eq!(glyph.pixels().count(), 16);
+        assert_eq!(lit_pixels(&glyph), vec![(0, 1), (7, 1)]);
+
+        let glyph = pixel_glyph(16, 1, 0, &[0x80, 0x01]);
+        assert_eq!(glyph.pixels().count(), 16);
+        assert_eq!(lit_pixels(&glyph), vec![(0, 0), (15, 0)]);
+    }
+
+    #[test]
+    fn it_iterates_pixels_of_glyphs_spanning_bytes() {
+        let glyph = pixel_glyph(9, 2, 0, &[0x00, 0x80, 0x80, 0x7f]);
+        assert_eq!(glyph.pixels().count(), 18);
+        assert_eq!(lit_pixels(&glyph), vec![(8, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn it_iterates_pixels_of_empty_glyphs() {
+        let glyph = pixel_glyph(0, 0, 0, &[]);
+        assert_eq!(glyph.pixels().count(), 0);
+    }
+
+    #[test]
+    fn it_iterates_pixels_below_the_baseline() {
+        let glyph = pixel_glyph(1, 3, -2, &[0x80, 0x00, 0x80]);
+        assert_eq!(lit_pixels(&glyph), vec![(0, 0), (0, -2)]);
+    }
+
+    #[cfg(feature = "embedded-graphics")]
+    #[test]
+    fn it_renders_embedded_graphics_pixels() {
+        use embedded_graphics::pixelcolor::BinaryColor;
+
+        let glyph = pixel_glyph(1, 2, -1, &[0x80, 0x00]);
+
+        assert_eq!(
+            glyph
+                .render_to(BinaryColor::On, BinaryColor::Off)
+                .collect::<Vec<_>>(),
+            vec![
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(0, 1), BinaryColor::Off),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_checks_padding_bits() {
+        let mut glyph = Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: None,
+            bounding_box: (3, 2, 0, 0),
+            bitmap: vec![0xe0a0],
+            bitmap_rows: 2,
+        };
+
+        assert!(glyph.bounding_box_contains_all_set_pixels());
+
+        glyph.bitmap = vec![0xe0a1];
+        assert!(!glyph.bounding_box_contains_all_set_pixels());
+
+        glyph.bounding_box = (8, 2, 0, 0);
+        assert!(glyph.bounding_box_contains_all_set_pixels());
+    }
+
+    #[test]
+    fn it_packs_bitmaps_like_the_parser() {
+        assert_eq!(
+            pack_bitmap(&[0x00, 0x18, 0x24, 0x24, 0x42, 0x7e]),
+            vec![0x00182424, 0x427e]
+        );
+        assert_eq!(pack_bitmap(&[]), vec![]);
+    }
+
+    #[test]
+    fn it_gets_pixels() {
+        let glyph = Glyph {
+            name: "test".to_string(),
+            charcode: 65,
+            device_width: None,
+            bounding_box: (3, 2, 0, 0),
+            bitmap: vec![0xa040],
+            bitmap_rows: 2,
+        };
+
+        assert_eq!(glyph.get_pixel(0, 0), Some(true));
+        assert_eq!(glyph.get_pixel(1, 0), Some(false));
+        assert_eq!(glyph.get_pixel(2, 0), Some(true));
+        assert_eq!(glyph.get_pixel(1, 1), Some(true));
+        assert_eq!(glyph.get_pixel(3, 0), None);
+        assert_eq!(glyph.get_pixel(0, 2), None);
+    }
+
     #[test]
     fn it_parses_a_single_char() {
         let chardata = r#"STARTCHAR ZZZZ
@@ -172,13 +637,35 @@ ENDCHAR"#;
                 Glyph {
                     name: "ZZZZ".to_string(),
                     charcode: 65,
+                    device_width: Some((8, 0)),
                     bitmap: vec![0x00000000, 0x18242442, 0x427e4242, 0x42420000],
+                    bitmap_rows: 16,
                     bounding_box: (8, 16, 0, -2),
                 }
             ))
         );
     }
 
+    #[test]
+    fn it_reads_bitmaps_with_more_rows_than_the_bounding_box() {
+        let chardata = "STARTCHAR A\nENCODING 65\nBBX 8 2 0 0\nBITMAP\nff\n81\n42\nENDCHAR";
+
+        let (_, glyph) = glyph(CompleteByteSlice(chardata.as_bytes())).unwrap();
+
+        assert_eq!(glyph.bitmap_rows, 3);
+        assert_eq!(glyph.bitmap_bytes(), vec![0xff, 0x81]);
+    }
+
+    #[test]
+    fn it_reads_bitmaps_with_fewer_rows_than_the_bounding_box() {
+        let chardata = "STARTCHAR A\nENCODING 65\nBBX 12 3 0 0\nBITMAP\nfff0\n8010\nENDCHAR";
+
+        let (_, glyph) = glyph(CompleteByteSlice(chardata.as_bytes())).unwrap();
+
+        assert_eq!(glyph.bitmap_rows, 2);
+        assert_eq!(glyph.bitmap_bytes(), vec![0xff, 0xf0, 0x80, 0x10, 0x00, 0x00]);
+    }
+
     #[test]
     fn it_parses_negative_encodings() {
         let chardata = r#"STARTCHAR 000
@@ -197,7 +684,9 @@ ENDCHAR"#;
                 EMPTY,
                 Glyph {
                     bitmap: vec![],
+                    bitmap_rows: 0,
                     bounding_box: (0, 0, 0, 0),
+                    device_width: Some((6, 0)),
                     charcode: -1i32,
                     name: "000".to_string(),
                 }
@@ -223,7 +712,9 @@ ENDCHAR"#;
                 EMPTY,
                 Glyph {
                     bitmap: vec![],
+                    bitmap_rows: 0,
                     bounding_box: (0, 0, 0, 0),
+                    device_width: Some((6, 0)),
                     charcode: 0,
                     name: "000".to_string(),
                 }
@@ -0,0 +1,69 @@
+use indexmap::IndexMap;
+
+use super::{BDFFont, Glyph};
+
+impl BDFFont {
+    /// Converts the font into a map from chars to glyphs that keeps the order of the glyphs in
+    /// the font.
+    ///
+    /// Unencoded glyphs are dropped. If multiple glyphs share an encoding the first one is kept,
+    /// just like in [`glyph`].
+    ///
+    /// [`glyph`]: #method.glyph
+    pub fn into_ordered_glyph_map(self) -> IndexMap<char, Glyph> {
+        let mut map = IndexMap::with_capacity(self.glyphs.len());
+
+        for glyph in self.glyphs {
+            if let Some(c) = glyph.encoding() {
+                map.entry(c).or_insert(glyph);
+            }
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use BDFParser;
+
+    #[test]
+    fn it_converts_fonts_into_ordered_maps() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "map"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR B
+ENCODING 66
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR B.alt
+ENCODING 66
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let map = font.into_ordered_glyph_map();
+
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec![&'B', &'A']);
+        assert_eq!(map[&'B'].name, "B");
+    }
+}
@@ -27,9 +27,18 @@ named!(
     )
 );
 
-named!(pub optional_comments<CompleteByteSlice, Vec<String>>, many0!(comment));
+named!(
+    pub optional_comments<CompleteByteSlice, Vec<String>>,
+    many0!(preceded!(opt!(multispace), comment))
+);
 
-named!(pub numchars<CompleteByteSlice, u32>, ws!(preceded!(tag!("CHARS"), parse_to_u32)));
+named!(
+    pub numchars<CompleteByteSlice, u32>,
+    ws!(preceded!(
+        alt!(tag!("CHARS_TOTAL") | tag!("CHARS") | tag!("NUMCHARS")),
+        parse_to_u32
+    ))
+);
 
 named!(pub take_until_line_ending<CompleteByteSlice, CompleteByteSlice>, alt_complete!(take_until!("\r\n") | take_until!("\n")));
 
@@ -71,4 +80,30 @@ mod tests {
             Ok((EMPTY, "".to_string()))
         );
     }
+
+    #[test]
+    fn it_parses_comments_separated_by_blank_lines() {
+        assert_eq!(
+            optional_comments(CompleteByteSlice(
+                b"\nCOMMENT a\n\r\n\nCOMMENT b\nSTARTFONT"
+            )),
+            Ok((
+                CompleteByteSlice(b"STARTFONT"),
+                vec!["a".to_string(), "b".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn it_parses_numchars_aliases() {
+        assert_eq!(numchars(CompleteByteSlice(b"CHARS 10\n")), Ok((EMPTY, 10)));
+        assert_eq!(
+            numchars(CompleteByteSlice(b"CHARS_TOTAL 20\n")),
+            Ok((EMPTY, 20))
+        );
+        assert_eq!(
+            numchars(CompleteByteSlice(b"NUMCHARS 30\n")),
+            Ok((EMPTY, 30))
+        );
+    }
 }
@@ -0,0 +1,132 @@
+use image::{Rgba, RgbaImage};
+use std::convert::TryFrom;
+
+use super::BDFFont;
+
+/// Draws text from a BDF font onto images from the `image` crate.
+#[derive(Debug, Clone, Copy)]
+pub struct BdfImageFont<'a> {
+    font: &'a BDFFont,
+}
+
+impl<'a> BdfImageFont<'a> {
+    pub fn new(font: &'a BDFFont) -> Self {
+        Self { font }
+    }
+
+    /// Draws `text` with its top left corner at `(x, y)`.
+    ///
+    /// Set pixels are drawn in `fg`. If `bg` is given, the bounding box of the text is filled
+    /// with it first. Lines are separated by `\n`, chars without a glyph are skipped and pixels
+    /// outside the image are clipped.
+    pub fn draw_text(
+        &self,
+        img: &mut RgbaImage,
+        text: &str,
+        x: u32,
+        y: u32,
+        fg: Rgba<u8>,
+        bg: Option<Rgba<u8>>,
+    ) {
+        let (width, height) = self.font.layout_text(text, |_, _| {});
+
+        if let Some(bg) = bg {
+            for py in y..y.saturating_add(height).min(img.height()) {
+                for px in x..x.saturating_add(width).min(img.width()) {
+                    img.put_pixel(px, py, bg);
+                }
+            }
+        }
+
+        self.font.layout_text(text, |px, py| {
+            let px = u32::try_from(i64::from(x) + i64::from(px));
+            let py = u32::try_from(i64::from(y) + i64::from(py));
+
+            if let (Ok(px), Ok(py)) = (px, py) {
+                if px < img.width() && py < img.height() {
+                    img.put_pixel(px, py, fg);
+                }
+            }
+        });
+    }
+}
+
+impl BDFFont {
+    /// Wraps the font to draw text onto images from the `image` crate.
+    pub fn to_image_crate_font(&self) -> BdfImageFont<'_> {
+        BdfImageFont::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "image"
+SIZE 3 75 75
+FONTBOUNDINGBOX 2 3 0 -1
+STARTPROPERTIES 2
+FONT_ASCENT 2
+FONT_DESCENT 1
+ENDPROPERTIES
+STARTCHAR i
+ENCODING 105
+DWIDTH 2 0
+BBX 1 2 0 0
+BITMAP
+80
+80
+ENDCHAR
+STARTCHAR comma
+ENCODING 44
+DWIDTH 2 0
+BBX 1 2 0 -1
+BITMAP
+80
+80
+ENDCHAR
+ENDFONT
+"#;
+
+    #[test]
+    fn it_draws_text() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+
+        let fg = Rgba([255, 255, 255, 255]);
+        let bg = Rgba([0, 0, 255, 255]);
+        let clear = Rgba([0, 0, 0, 0]);
+
+        let mut img = RgbaImage::new(5, 4);
+        font.to_image_crate_font()
+            .draw_text(&mut img, "i,", 1, 0, fg, Some(bg));
+
+        let expected = [
+            [clear, fg, bg, bg, bg],
+            [clear, fg, bg, fg, bg],
+            [clear, bg, bg, fg, bg],
+            [clear, clear, clear, clear, clear],
+        ];
+
+        for (y, row) in expected.iter().enumerate() {
+            for (x, pixel) in row.iter().enumerate() {
+                assert_eq!(img.get_pixel(x as u32, y as u32), pixel, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn it_clips_text_at_large_positions() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+        let fg = Rgba([255, 255, 255, 255]);
+
+        let mut img = RgbaImage::new(5, 4);
+        for &(x, y) in &[(u32::MAX, u32::MAX), (i32::MAX as u32 + 1, 0)] {
+            font.to_image_crate_font()
+                .draw_text(&mut img, "i,", x, y, fg, Some(fg));
+        }
+
+        assert!(img.pixels().all(|&pixel| pixel == Rgba([0, 0, 0, 0])));
+    }
+}
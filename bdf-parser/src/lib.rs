@@ -1,16 +1,65 @@
 #[macro_use]
 extern crate nom;
+#[cfg(feature = "embedded-graphics")]
+extern crate embedded_graphics;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "indexmap")]
+extern crate indexmap;
 
+mod analysis;
+mod antialias;
+mod binary;
+mod cache;
+mod cff;
+mod compliance;
+mod epd;
+mod error;
 mod glyph;
+#[cfg(feature = "indexmap")]
+mod glyph_map;
 mod helpers;
+#[cfg(feature = "image")]
+mod image_font;
+mod lvgl;
 mod metadata;
+mod metrics;
 mod properties;
+#[cfg(feature = "qoi")]
+mod qoi;
+mod render;
+mod rust_include;
+mod transform;
+mod ttf;
+mod unicode;
+mod unifont;
+mod weight;
+mod writer;
+mod xml;
+mod zapf;
 
+pub use analysis::EncodingConflict;
+pub use antialias::{AntiAliasedFont, AntiAliasedGlyph};
+pub use binary::{CompactExportError, GlyphDescriptor, GlyphIndexEntry};
+pub use cache::BdfFontCache;
+pub use cff::CffExportError;
+pub use compliance::{ComplianceReport, MetadataWarning};
+pub use epd::EpdExportError;
+pub use error::BdfParseError;
 use glyph::*;
+pub use glyph::{Glyph, GlyphPixels};
 use helpers::*;
+#[cfg(feature = "image")]
+pub use image_font::BdfImageFont;
 use metadata::*;
+pub use metadata::{Metadata, MetadataBuilder};
+pub use metrics::{CharInfo, HintingMetrics, ScaledFontMetrics};
 use nom::types::CompleteByteSlice;
 use properties::*;
+pub use properties::{Properties, PropertyValue};
+pub use ttf::TtfExportError;
+pub use unicode::UnicodeScript;
+pub use weight::WeightClass;
 
 pub type BoundingBox = (u32, u32, i32, i32);
 
@@ -18,7 +67,84 @@ pub type BoundingBox = (u32, u32, i32, i32);
 pub struct BDFFont {
     metadata: Option<Metadata>,
     glyphs: Vec<Glyph>,
-    properties: Option<Properties>
+    properties: Option<Properties>,
+    chars: Option<u32>,
+    endfont: bool,
+}
+
+impl BDFFont {
+    /// Returns the glyph for the given character.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.iter().find(|glyph| glyph.charcode == c as i32)
+    }
+
+    /// Returns the state of the pixel at `(x, y)` in the glyph for `c`, relative to the top left
+    /// corner of the glyph bounding box.
+    ///
+    /// Returns `None` if the font has no glyph for `c` or the coordinates are outside the
+    /// bounding box.
+    pub fn pixel_at(&self, c: char, x: u32, y: u32) -> Option<bool> {
+        self.glyph(c)?.get_pixel(x, y)
+    }
+
+    /// Returns all glyphs without an encoding, in font order.
+    ///
+    /// These glyphs can't be accessed by [`glyph`], but can still be looked up by name.
+    ///
+    /// [`glyph`]: #method.glyph
+    pub fn unencoded_glyphs(&self) -> impl Iterator<Item = &Glyph> {
+        self.glyphs
+            .iter()
+            .filter(|glyph| glyph.encoding().is_none())
+    }
+
+    /// Returns the glyph at `index` in font order.
+    pub fn glyph_at_index(&self, index: usize) -> Option<&Glyph> {
+        self.glyphs.get(index)
+    }
+
+    /// Returns the encodings of all glyphs in font order, with `None` for unencoded glyphs.
+    ///
+    /// The indices of the returned `Vec` match the indices used by [`glyph_at_index`].
+    ///
+    /// [`glyph_at_index`]: #method.glyph_at_index
+    pub fn all_encodings(&self) -> Vec<Option<char>> {
+        self.glyphs.iter().map(|glyph| glyph.encoding()).collect()
+    }
+
+    /// Returns the glyph for `c`, falling back to the glyph for `fallback` and then to the glyph
+    /// declared by the `DEFAULT_CHAR` property.
+    pub fn get_or_fallback_glyph(&self, c: char, fallback: char) -> Option<&Glyph> {
+        self.glyph(c).or_else(|| self.glyph(fallback)).or_else(|| {
+            let default_char = self.int_property("DEFAULT_CHAR")?;
+
+            self.glyphs
+                .iter()
+                .find(|glyph| glyph.charcode == default_char)
+        })
+    }
+
+    /// Returns the `DEVICE_FONT_NAME` property.
+    ///
+    /// The property names a Type 1 or TrueType font that renderers can fall back to for
+    /// characters that aren't available in the BDF font.
+    pub fn device_font_name(&self) -> Option<&str> {
+        self.text_property("DEVICE_FONT_NAME")
+    }
+
+    pub(crate) fn int_property(&self, name: &str) -> Option<i32> {
+        match self.properties.as_ref()?.get(name)? {
+            PropertyValue::Int(value) => Some(*value),
+            PropertyValue::Text(_) => None,
+        }
+    }
+
+    pub(crate) fn text_property(&self, name: &str) -> Option<&str> {
+        match self.properties.as_ref()?.get(name)? {
+            PropertyValue::Text(value) => Some(value),
+            PropertyValue::Int(_) => None,
+        }
+    }
 }
 
 pub struct BDFParser<'a> {
@@ -26,27 +152,212 @@ pub struct BDFParser<'a> {
 }
 
 impl<'a> BDFParser<'a> {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(source: &'a str) -> Self {
         Self { source }
     }
 
-    pub fn parse(&self) -> Result<(CompleteByteSlice, BDFFont), nom::Err<CompleteByteSlice>> {
-        bdf(CompleteByteSlice(&self.source.as_bytes()))
+    /// Parses the source with the underlying nom parser.
+    ///
+    /// Unparsed input is returned next to the font and errors are raw nom errors. Use
+    /// [`parse_font`] instead, unless the nom error is needed.
+    ///
+    /// [`parse_font`]: #method.parse_font
+    pub fn parse(&self) -> Result<(CompleteByteSlice<'_>, BDFFont), nom::Err<CompleteByteSlice<'_>>> {
+        bdf(CompleteByteSlice(self.source.as_bytes()))
+    }
+
+    /// Parses the source into a font, treating unparsed trailing data as an error.
+    ///
+    /// This is the recommended way to parse a font. Errors contain the byte offset of the
+    /// problem and a message that describes it in terms of the BDF file.
+    pub fn parse_font(&self) -> Result<BDFFont, BdfParseError> {
+        self.complete(self.parse())
+    }
+
+    /// Parses the source into a font, recovering from common structural errors.
+    ///
+    /// A properties block without `ENDPROPERTIES` is ended at the first `STARTCHAR`, `CHARS` or
+    /// `ENDFONT` line. Every recovered error is returned as a warning next to the font.
+    pub fn parse_recoverable(&self) -> Result<(BDFFont, Vec<String>), BdfParseError> {
+        let (font, missing_endproperties) =
+            self.complete(bdf_recoverable(CompleteByteSlice(self.source.as_bytes())))?;
+
+        let mut warnings = Vec::new();
+        if missing_endproperties {
+            warnings.push("Missing ENDPROPERTIES".to_string());
+        }
+
+        Ok((font, warnings))
+    }
+
+    /// Parses a source that contains multiple size variants of the same font.
+    ///
+    /// Each additional `SIZE` declaration after the glyphs of the previous variant starts a new
+    /// variant, which can be followed by its own `FONTBOUNDINGBOX`, properties, `CHARS` and
+    /// glyphs and ends at the next `SIZE` or `ENDFONT`. Variants share the version and name of
+    /// the first header and inherit the bounding box and properties of the previous variant if
+    /// they don't declare their own. Variants with the same size and resolution are merged.
+    pub fn parse_all(&self) -> Result<Vec<BDFFont>, BdfParseError> {
+        let (first, variants, endfont) =
+            self.complete(bdf_all(CompleteByteSlice(self.source.as_bytes())))?;
+
+        let mut fonts = vec![BDFFont { endfont, ..first }];
+
+        for variant in variants {
+            let existing = fonts.iter_mut().find(|font| {
+                font.metadata.as_ref().map(|metadata| metadata.size) == Some(variant.size)
+            });
+
+            if let Some(font) = existing {
+                font.glyphs.extend(variant.glyphs);
+                font.chars = match (font.chars, variant.chars) {
+                    (Some(chars), Some(more)) => Some(chars + more),
+                    (chars, more) => chars.or(more),
+                };
+                continue;
+            }
+
+            let previous = &fonts[fonts.len() - 1];
+            let metadata = previous
+                .metadata
+                .clone()
+                .unwrap_or_else(|| Metadata::builder().build());
+            let (point_size, x_resolution, y_resolution) = variant.size;
+
+            let font = BDFFont {
+                metadata: Some(
+                    metadata
+                        .clone()
+                        .with_point_size(point_size)
+                        .with_resolution(x_resolution, y_resolution)
+                        .with_bounding_box(variant.bounding_box.unwrap_or(metadata.bounding_box)),
+                ),
+                properties: variant.properties.or_else(|| previous.properties.clone()),
+                chars: variant.chars,
+                glyphs: variant.glyphs,
+                endfont,
+            };
+            fonts.push(font);
+        }
+
+        Ok(fonts)
+    }
+
+    fn complete<T>(
+        &self,
+        result: Result<(CompleteByteSlice<'_>, T), nom::Err<CompleteByteSlice<'_>>>,
+    ) -> Result<T, BdfParseError> {
+        match result {
+            Ok((rest, _)) if !rest.is_empty() => Err(self.leftover_error(rest)),
+            Ok((_, value)) => Ok(value),
+            Err(error) => Err(BdfParseError::from_nom(self.source, error)),
+        }
+    }
+
+    // Optional sections that fail to parse are left over as unparsed input. Parsing the
+    // section again by itself points the error at the actual problem.
+    fn leftover_error(&self, rest: CompleteByteSlice<'_>) -> BdfParseError {
+        let start = rest
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let section = CompleteByteSlice(&rest[start..]);
+
+        let result = if section.starts_with(b"STARTFONT") {
+            header(section).map(|_| ())
+        } else if section.starts_with(b"STARTPROPERTIES") {
+            properties(section).map(|_| ())
+        } else if section.starts_with(b"STARTCHAR") {
+            glyph(section).map(|_| ())
+        } else {
+            Ok(())
+        };
+
+        match result {
+            Err(error) => BdfParseError::from_nom(self.source, error),
+            Ok(()) => {
+                BdfParseError::unexpected_data(self.source, self.source.len() - section.len())
+            }
+        }
     }
 }
 
 named!(
     inner_bdf<CompleteByteSlice, BDFFont>,
     ws!(do_parse!(
-        metadata: opt!(header) >> properties: opt!(properties) >> opt!(numchars) >> glyphs: many0!(glyph) >> ({
-            BDFFont { properties, metadata, glyphs }
+        metadata: opt!(header) >> properties: opt!(properties) >> chars: opt!(numchars) >> glyphs: many0!(glyph) >> ({
+            BDFFont { properties, metadata, glyphs, chars, endfont: false }
         })
     ))
 );
 
+// Everything after `ENDFONT`, like trailers appended by font tools, is ignored.
+named!(
+    endfont<CompleteByteSlice, CompleteByteSlice>,
+    terminated!(tag!("ENDFONT"), call!(nom::rest))
+);
+
 named!(
     bdf<CompleteByteSlice, BDFFont>,
-    alt_complete!(ws!(terminated!(inner_bdf, tag!("ENDFONT"))) | inner_bdf)
+    preceded!(
+        optional_comments,
+        alt_complete!(
+            map!(ws!(terminated!(inner_bdf, endfont)), |font| BDFFont { endfont: true, ..font }) | inner_bdf
+        )
+    )
+);
+
+named!(
+    inner_bdf_recoverable<CompleteByteSlice, (BDFFont, bool)>,
+    ws!(do_parse!(
+        metadata: opt!(header) >> properties: opt!(properties_recoverable) >> chars: opt!(numchars) >> glyphs: many0!(glyph) >> ({
+            let (properties, recovered) = match properties {
+                Some((properties, recovered)) => (Some(properties), recovered),
+                None => (None, false),
+            };
+
+            (BDFFont { properties, metadata, glyphs, chars, endfont: false }, recovered)
+        })
+    ))
+);
+
+named!(
+    bdf_recoverable<CompleteByteSlice, (BDFFont, bool)>,
+    preceded!(
+        optional_comments,
+        alt_complete!(
+            map!(ws!(terminated!(inner_bdf_recoverable, endfont)), |(font, recovered)| (BDFFont { endfont: true, ..font }, recovered)) | inner_bdf_recoverable
+        )
+    )
+);
+
+/// An additional size variant, see `BDFParser::parse_all`.
+struct SizeVariant {
+    size: FontSize,
+    bounding_box: Option<BoundingBox>,
+    properties: Option<Properties>,
+    chars: Option<u32>,
+    glyphs: Vec<Glyph>,
+}
+
+named!(
+    size_variant<CompleteByteSlice, SizeVariant>,
+    ws!(do_parse!(
+        header: size_variant_header >> properties: opt!(properties) >> chars: opt!(numchars) >> glyphs: many0!(glyph) >> ({
+            SizeVariant { size: header.0, bounding_box: header.1, properties, chars, glyphs }
+        })
+    ))
+);
+
+named!(
+    bdf_all<CompleteByteSlice, (BDFFont, Vec<SizeVariant>, bool)>,
+    preceded!(
+        optional_comments,
+        ws!(do_parse!(
+            font: inner_bdf >> variants: many0!(size_variant) >> endfont: opt!(endfont) >> ((font, variants, endfont.is_some()))
+        ))
+    )
 );
 
 #[cfg(test)]
@@ -88,7 +399,7 @@ ENDCHAR
 ENDFONT
 "#;
 
-        let out = bdf(CompleteByteSlice(&chardata.as_bytes()));
+        let out = bdf(CompleteByteSlice(chardata.as_bytes()));
 
         assert_eq!(
             out,
@@ -104,13 +415,17 @@ ENDFONT
                     glyphs: vec![
                         Glyph {
                             bitmap: vec![0x1f01],
+                            bitmap_rows: 2,
                             bounding_box: (8, 8, 0, 0),
+                            device_width: Some((8, 0)),
                             charcode: 64,
                             name: "000".to_string(),
                         },
                         Glyph {
                             bitmap: vec![0x2f02],
+                            bitmap_rows: 2,
                             bounding_box: (8, 8, 0, 0),
+                            device_width: Some((8, 0)),
                             charcode: 64,
                             name: "000".to_string(),
                         },
@@ -119,7 +434,9 @@ ENDFONT
                         "COPYRIGHT".into() => PropertyValue::Text("https://github.com/iconic/open-iconic, SIL OPEN FONT LICENSE".into()),
                         "FONT_ASCENT".into() => PropertyValue::Int(0),
                         "FONT_DESCENT".into() => PropertyValue::Int(0),
-                    })
+                    }),
+                    chars: None,
+                    endfont: true,
                 }
             ))
         );
@@ -154,7 +471,7 @@ BITMAP
 ENDCHAR
 "#;
 
-        let out = bdf(CompleteByteSlice(&chardata.as_bytes()));
+        let out = bdf(CompleteByteSlice(chardata.as_bytes()));
 
         assert_eq!(
             out,
@@ -170,13 +487,17 @@ ENDCHAR
                     glyphs: vec![
                         Glyph {
                             bitmap: vec![0x1f01],
+                            bitmap_rows: 2,
                             bounding_box: (8, 8, 0, 0),
+                            device_width: Some((8, 0)),
                             charcode: 64,
                             name: "000".to_string(),
                         },
                         Glyph {
                             bitmap: vec![0x2f02],
+                            bitmap_rows: 2,
                             bounding_box: (8, 8, 0, 0),
+                            device_width: Some((8, 0)),
                             charcode: 64,
                             name: "000".to_string(),
                         },
@@ -185,7 +506,9 @@ ENDCHAR
                         "COPYRIGHT".into() => PropertyValue::Text("https://github.com/iconic/open-iconic, SIL OPEN FONT LICENSE".into()),
                         "FONT_ASCENT".into() => PropertyValue::Int(0),
                         "FONT_DESCENT".into() => PropertyValue::Int(0),
-                    })
+                    }),
+                    chars: None,
+                    endfont: false,
                 }
             ))
         );
@@ -194,7 +517,7 @@ ENDCHAR
     #[test]
     fn it_handles_windows_line_endings() {
         let windows_line_endings = "STARTFONT 2.1\r\nFONT \"windows_test\"\r\nSIZE 10 96 96\r\nFONTBOUNDINGBOX 8 16 0 -4\r\nCHARS 256\r\nSTARTCHAR 0\r\nENCODING 0\r\nSWIDTH 600 0\r\nDWIDTH 8 0\r\nBBX 8 16 0 -4\r\nBITMAP\r\nD5\r\nENDCHAR\r\nENDFONT\r\n";
-        let out = bdf(CompleteByteSlice(&windows_line_endings.as_bytes()));
+        let out = bdf(CompleteByteSlice(windows_line_endings.as_bytes()));
 
         assert_eq!(
             out,
@@ -210,14 +533,332 @@ ENDCHAR
                     glyphs: vec![
                         Glyph {
                             bitmap: vec![0xd5],
+                            bitmap_rows: 1,
                             bounding_box: (8, 16, 0, -4),
+                            device_width: Some((8, 0)),
                             charcode: 0,
                             name: "0".to_string(),
                         },
                     ],
-                    properties: None
+                    properties: None,
+                    chars: Some(256),
+                    endfont: true,
                 }
             ))
         );
     }
+
+    #[test]
+    fn it_reads_the_device_font_name() {
+        let chardata = r#"STARTFONT 2.1
+FONT "device"
+SIZE 16 75 75
+FONTBOUNDINGBOX 16 24 0 0
+STARTPROPERTIES 1
+DEVICE_FONT_NAME "Helvetica"
+ENDPROPERTIES
+ENDFONT
+"#;
+
+        let (_, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+        assert_eq!(font.device_font_name(), Some("Helvetica"));
+
+        let font = BDFFont { properties: None, ..font };
+        assert_eq!(font.device_font_name(), None);
+    }
+
+    #[test]
+    fn it_parses_comments_before_startfont() {
+        let chardata = r#"
+COMMENT Copyright (c) 1999
+
+COMMENT
+STARTFONT 2.1
+FONT "comments"
+SIZE 16 75 75
+FONTBOUNDINGBOX 16 24 0 0
+CHARS 0
+ENDFONT
+"#;
+
+        let (rest, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+
+        assert_eq!(rest, EMPTY);
+        assert_eq!(font.metadata.unwrap().name, "\"comments\"");
+    }
+
+    #[test]
+    fn it_falls_back_to_other_glyphs() {
+        let chardata = r#"STARTFONT 2.1
+FONT "fallback"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTPROPERTIES 1
+DEFAULT_CHAR 0
+ENDPROPERTIES
+STARTCHAR default
+ENCODING 0
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR question
+ENCODING 63
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let (_, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+        let name = |glyph: Option<&Glyph>| glyph.map(|glyph| glyph.name.clone());
+
+        assert_eq!(name(font.get_or_fallback_glyph('A', '?')), Some("A".into()));
+        assert_eq!(name(font.get_or_fallback_glyph('B', '?')), Some("question".into()));
+        assert_eq!(name(font.get_or_fallback_glyph('B', 'C')), Some("default".into()));
+
+        let font = BDFFont { properties: None, ..font };
+        assert_eq!(name(font.get_or_fallback_glyph('B', 'C')), None);
+    }
+
+    #[test]
+    fn it_recovers_from_missing_endproperties() {
+        let chardata = r#"STARTFONT 2.1
+FONT "recover"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTPROPERTIES 2
+CHARSET_REGISTRY "ISO10646"
+DEFAULT_CHAR 65
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let parser = BDFParser::from_str(chardata);
+        assert!(parser.parse_font().is_err());
+
+        let (font, warnings) = parser.parse_recoverable().unwrap();
+        assert_eq!(warnings, vec!["Missing ENDPROPERTIES".to_string()]);
+        assert_eq!(font.text_property("CHARSET_REGISTRY"), Some("ISO10646"));
+        assert_eq!(font.int_property("DEFAULT_CHAR"), Some(65));
+        assert_eq!(font.glyphs.len(), 1);
+        assert!(font.endfont);
+
+        let fixed = chardata.replace("STARTCHAR", "ENDPROPERTIES\nSTARTCHAR");
+        let (font, warnings) = BDFParser::from_str(&fixed).parse_recoverable().unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(font, BDFParser::from_str(&fixed).parse_font().unwrap());
+    }
+
+    #[test]
+    fn it_unescapes_doubled_quotes_in_properties() {
+        let chardata = r#"STARTFONT 2.1
+FONT "quotes"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTPROPERTIES 2
+COPYRIGHT "The ""quoted"" font"
+NOTICE ""
+ENDPROPERTIES
+ENDFONT
+"#;
+
+        let font = BDFParser::from_str(chardata).parse_font().unwrap();
+        assert_eq!(font.text_property("COPYRIGHT"), Some("The \"quoted\" font"));
+        assert_eq!(font.text_property("NOTICE"), Some(""));
+    }
+
+    #[test]
+    fn it_returns_pixels_of_glyphs() {
+        let chardata = r#"STARTFONT 2.1
+FONT "pixels"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 2 0 0
+STARTCHAR A
+ENCODING 65
+BBX 3 2 0 0
+BITMAP
+40
+A0
+ENDCHAR
+ENDFONT
+"#;
+
+        let (_, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+
+        assert_eq!(font.pixel_at('A', 1, 0), Some(true));
+        assert_eq!(font.pixel_at('A', 0, 0), Some(false));
+        assert_eq!(font.pixel_at('A', 2, 1), Some(true));
+        assert_eq!(font.pixel_at('A', 3, 0), None);
+        assert_eq!(font.pixel_at('A', 0, 2), None);
+        assert_eq!(font.pixel_at('B', 0, 0), None);
+    }
+
+    #[test]
+    fn it_lists_unencoded_glyphs() {
+        let chardata = r#"STARTFONT 2.1
+FONT "unencoded"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR ligature.fi
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR ligature.fl
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let (_, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+        let names: Vec<_> = font.unencoded_glyphs().map(|glyph| glyph.name.as_str()).collect();
+
+        assert_eq!(names, vec!["ligature.fi", "ligature.fl"]);
+    }
+
+    #[test]
+    fn it_lists_all_encodings_in_font_order() {
+        let chardata = r#"STARTFONT 2.1
+FONT "encodings"
+SIZE 16 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR B
+ENCODING 66
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR ligature.fi
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let (_, font) = bdf(CompleteByteSlice(chardata.as_bytes())).unwrap();
+        let encodings = font.all_encodings();
+
+        assert_eq!(encodings, vec![Some('B'), None, Some('A')]);
+        assert_eq!(font.glyph_at_index(1).map(|glyph| glyph.name.as_str()), Some("ligature.fi"));
+        assert_eq!(font.glyph_at_index(2).unwrap().encoding(), encodings[2]);
+        assert_eq!(font.glyph_at_index(3), None);
+    }
+
+    #[test]
+    fn it_parses_multiple_size_variants() {
+        let chardata = r#"STARTFONT 2.1
+FONT "variants"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 -2
+STARTPROPERTIES 1
+FONT_ASCENT 6
+ENDPROPERTIES
+CHARS 1
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+SIZE 8 100 100
+FONTBOUNDINGBOX 11 11 0 -3
+CHARS 1
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+SIZE 8 75 75
+STARTCHAR B
+ENCODING 66
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let fonts = BDFParser::from_str(chardata).parse_all().unwrap();
+        assert_eq!(fonts.len(), 2);
+
+        let metadata = |font: &BDFFont| font.metadata.clone().unwrap();
+        assert_eq!(metadata(&fonts[0]).size, (8, 75, 75));
+        assert_eq!(metadata(&fonts[0]).bounding_box, (8, 8, 0, -2));
+        assert_eq!(fonts[0].glyphs.len(), 2);
+        assert!(fonts[0].endfont);
+
+        assert_eq!(metadata(&fonts[1]).name, "\"variants\"");
+        assert_eq!(metadata(&fonts[1]).size, (8, 100, 100));
+        assert_eq!(metadata(&fonts[1]).bounding_box, (11, 11, 0, -3));
+        assert_eq!(fonts[1].int_property("FONT_ASCENT"), Some(6));
+        assert_eq!(fonts[1].chars, Some(1));
+        assert_eq!(fonts[1].glyphs.len(), 1);
+        assert!(fonts[1].endfont);
+    }
+
+    #[test]
+    fn it_parses_single_fonts_with_parse_all() {
+        let chardata = r#"STARTFONT 2.1
+FONT "single"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 -2
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+        let parser = BDFParser::from_str(chardata);
+        assert_eq!(parser.parse_all().unwrap(), vec![parser.parse_font().unwrap()]);
+    }
+
+    #[test]
+    fn it_ignores_data_after_endfont() {
+        let chardata = r#"STARTFONT 2.1
+FONT "trailer"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 -2
+STARTCHAR A
+ENCODING 65
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+        let font = BDFParser::from_str(chardata).parse_font().unwrap();
+
+        for trailer in &["\n\n\n", "COMMENT generated by fonttool\n", "\u{1a}", "garbage"] {
+            let source = format!("{}{}", chardata, trailer);
+            let parser = BDFParser::from_str(&source);
+
+            assert_eq!(parser.parse(), Ok((EMPTY, font.clone())));
+            assert_eq!(parser.parse_font().unwrap(), font);
+            assert_eq!(parser.parse_recoverable().unwrap(), (font.clone(), vec![]));
+            assert_eq!(parser.parse_all().unwrap(), vec![font.clone()]);
+        }
+
+        let unterminated = chardata.replace("ENDFONT\n", "garbage");
+        assert!(BDFParser::from_str(&unterminated).parse_font().is_err());
+    }
 }
@@ -0,0 +1,252 @@
+use std::fmt::Write;
+
+use super::BDFFont;
+
+impl BDFFont {
+    /// Generates C source code that defines an LVGL `lv_font_t` named `array_prefix`.
+    ///
+    /// The font uses the `lv_font_fmt_txt` format with 1 bit per pixel, like fonts converted
+    /// with `lv_font_conv`. All arrays are prefixed with `array_prefix`, which must be a valid C
+    /// identifier. Unencoded glyphs are skipped.
+    pub fn to_lvgl_font(&self, array_prefix: &str) -> String {
+        let glyphs = self.lookup_glyphs();
+        let metrics = self.compute_hinting_metrics();
+        let mut out = String::new();
+
+        writeln!(out, "#include \"lvgl.h\"").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "static LV_ATTRIBUTE_LARGE_CONST const uint8_t {}_glyph_bitmap[] = {{",
+            array_prefix
+        )
+        .unwrap();
+
+        let mut bitmap_index = 0;
+        let mut descriptors = Vec::new();
+
+        for &(c, glyph) in &glyphs {
+            let (width, height, x_offset, y_offset) = glyph.bounding_box;
+
+            // LVGL stores the pixels without padding at the end of each row
+            let mut bytes = vec![0u8; (width as usize * height as usize).div_ceil(8)];
+            for (i, (x, y)) in (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .enumerate()
+            {
+                if glyph.get_pixel(x, y) == Some(true) {
+                    bytes[i / 8] |= 0x80 >> (i % 8);
+                }
+            }
+
+            writeln!(out, "    /* U+{:04X} */", c as u32).unwrap();
+            for row in bytes.chunks(16) {
+                let row: Vec<String> = row.iter().map(|byte| format!("0x{:02x}", byte)).collect();
+                writeln!(out, "    {},", row.join(", ")).unwrap();
+            }
+
+            descriptors.push(format!(
+                "{{.bitmap_index = {}, .adv_w = {}, .box_w = {}, .box_h = {}, .ofs_x = {}, .ofs_y = {}}}",
+                bitmap_index,
+                glyph.advance_width() * 16,
+                width,
+                height,
+                x_offset,
+                y_offset
+            ));
+            bitmap_index += bytes.len();
+        }
+
+        writeln!(out, "}};").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "static const lv_font_fmt_txt_glyph_dsc_t {}_glyph_dsc[] = {{",
+            array_prefix
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    {{.bitmap_index = 0, .adv_w = 0, .box_w = 0, .box_h = 0, .ofs_x = 0, .ofs_y = 0}} /* id = 0 reserved */,"
+        )
+        .unwrap();
+        for descriptor in descriptors {
+            writeln!(out, "    {},", descriptor).unwrap();
+        }
+        writeln!(out, "}};").unwrap();
+        writeln!(out).unwrap();
+
+        // Sparse cmaps store the codepoints as 16 bit offsets from the start of the range
+        let mut cmaps: Vec<(u32, usize, Vec<u32>)> = Vec::new();
+        for (id, &(c, _)) in glyphs.iter().enumerate() {
+            let c = c as u32;
+            match cmaps.last_mut() {
+                Some(&mut (start, _, ref mut offsets)) if c - start <= 0xffff => {
+                    offsets.push(c - start)
+                }
+                _ => cmaps.push((c, id + 1, vec![0])),
+            }
+        }
+
+        for (i, (_, _, offsets)) in cmaps.iter().enumerate() {
+            let offsets: Vec<String> = offsets.iter().map(|offset| offset.to_string()).collect();
+            writeln!(
+                out,
+                "static const uint16_t {}_unicode_list_{}[] = {{{}}};",
+                array_prefix,
+                i,
+                offsets.join(", ")
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "static const lv_font_fmt_txt_cmap_t {}_cmaps[] = {{",
+            array_prefix
+        )
+        .unwrap();
+        for (i, (start, glyph_id_start, offsets)) in cmaps.iter().enumerate() {
+            writeln!(
+                out,
+                "    {{.range_start = {}, .range_length = {}, .glyph_id_start = {}, .unicode_list = {}_unicode_list_{}, .glyph_id_ofs_list = NULL, .list_length = {}, .type = LV_FONT_FMT_TXT_CMAP_SPARSE_TINY}},",
+                start,
+                offsets[offsets.len() - 1] + 1,
+                glyph_id_start,
+                array_prefix,
+                i,
+                offsets.len()
+            )
+            .unwrap();
+        }
+        writeln!(out, "}};").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "#if LVGL_VERSION_MAJOR == 8").unwrap();
+        writeln!(
+            out,
+            "static lv_font_fmt_txt_glyph_cache_t {}_cache;",
+            array_prefix
+        )
+        .unwrap();
+        writeln!(out, "#endif").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(
+            out,
+            "static lv_font_fmt_txt_dsc_t {}_font_dsc = {{",
+            array_prefix
+        )
+        .unwrap();
+        writeln!(out, "    .glyph_bitmap = {}_glyph_bitmap,", array_prefix).unwrap();
+        writeln!(out, "    .glyph_dsc = {}_glyph_dsc,", array_prefix).unwrap();
+        writeln!(out, "    .cmaps = {}_cmaps,", array_prefix).unwrap();
+        writeln!(out, "    .kern_dsc = NULL,").unwrap();
+        writeln!(out, "    .kern_scale = 0,").unwrap();
+        writeln!(out, "    .cmap_num = {},", cmaps.len()).unwrap();
+        writeln!(out, "    .bpp = 1,").unwrap();
+        writeln!(out, "    .kern_classes = 0,").unwrap();
+        writeln!(out, "    .bitmap_format = 0,").unwrap();
+        writeln!(out, "#if LVGL_VERSION_MAJOR == 8").unwrap();
+        writeln!(out, "    .cache = &{}_cache,", array_prefix).unwrap();
+        writeln!(out, "#endif").unwrap();
+        writeln!(out, "}};").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "const lv_font_t {} = {{", array_prefix).unwrap();
+        writeln!(out, "    .get_glyph_dsc = lv_font_get_glyph_dsc_fmt_txt,").unwrap();
+        writeln!(out, "    .get_glyph_bitmap = lv_font_get_bitmap_fmt_txt,").unwrap();
+        writeln!(
+            out,
+            "    .line_height = {},",
+            (metrics.ascender + metrics.descender).max(0)
+        )
+        .unwrap();
+        writeln!(out, "    .base_line = {},", metrics.descender).unwrap();
+        writeln!(out, "    .subpx = LV_FONT_SUBPX_NONE,").unwrap();
+        writeln!(
+            out,
+            "    .underline_position = {},",
+            metrics.underline_position
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    .underline_thickness = {},",
+            metrics.underline_thickness
+        )
+        .unwrap();
+        writeln!(out, "    .dsc = &{}_font_dsc,", array_prefix).unwrap();
+        writeln!(out, "}};").unwrap();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use BDFParser;
+
+    #[test]
+    fn it_exports_lvgl_fonts() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "lvgl"
+SIZE 8 75 75
+FONTBOUNDINGBOX 4 4 0 -1
+STARTCHAR B
+ENCODING 66
+DWIDTH 4 0
+BBX 3 3 0 0
+BITMAP
+C0
+A0
+C0
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+DWIDTH 4 0
+BBX 3 2 1 -1
+BITMAP
+40
+A0
+ENDCHAR
+STARTCHAR emoji
+ENCODING 128512
+DWIDTH 4 0
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let c = font.to_lvgl_font("font_test");
+
+        for line in &[
+            "static LV_ATTRIBUTE_LARGE_CONST const uint8_t font_test_glyph_bitmap[] = {",
+            "    /* U+0041 */\n    0x54,",
+            "    /* U+0042 */\n    0xd7, 0x00,",
+            "    {.bitmap_index = 0, .adv_w = 64, .box_w = 3, .box_h = 2, .ofs_x = 1, .ofs_y = -1},",
+            "    {.bitmap_index = 1, .adv_w = 64, .box_w = 3, .box_h = 3, .ofs_x = 0, .ofs_y = 0},",
+            "    {.bitmap_index = 3, .adv_w = 64, .box_w = 0, .box_h = 0, .ofs_x = 0, .ofs_y = 0},",
+            "static const uint16_t font_test_unicode_list_0[] = {0, 1};",
+            "static const uint16_t font_test_unicode_list_1[] = {0};",
+            "    {.range_start = 65, .range_length = 2, .glyph_id_start = 1, .unicode_list = font_test_unicode_list_0, .glyph_id_ofs_list = NULL, .list_length = 2, .type = LV_FONT_FMT_TXT_CMAP_SPARSE_TINY},",
+            "    {.range_start = 128512, .range_length = 1, .glyph_id_start = 3, .unicode_list = font_test_unicode_list_1, .glyph_id_ofs_list = NULL, .list_length = 1, .type = LV_FONT_FMT_TXT_CMAP_SPARSE_TINY},",
+            "    .cmap_num = 2,",
+            "const lv_font_t font_test = {",
+            "    .line_height = 4,",
+            "    .base_line = 1,",
+            "    .dsc = &font_test_font_dsc,",
+        ] {
+            assert!(c.contains(line), "missing {:?} in:\n{}", line, c);
+        }
+    }
+}
@@ -13,6 +13,111 @@ pub struct Metadata {
     pub bounding_box: BoundingBox,
 }
 
+impl Metadata {
+    /// Returns a builder to construct metadata.
+    pub fn builder() -> MetadataBuilder {
+        MetadataBuilder::default()
+    }
+
+    /// Returns a copy with the version replaced.
+    pub fn with_version(self, version: f32) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Returns a copy with the name replaced.
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..self
+        }
+    }
+
+    /// Returns a copy with the point size replaced.
+    pub fn with_point_size(self, point_size: i32) -> Self {
+        let (_, x_resolution, y_resolution) = self.size;
+        Self {
+            size: (point_size, x_resolution, y_resolution),
+            ..self
+        }
+    }
+
+    /// Returns a copy with the horizontal and vertical resolution replaced.
+    pub fn with_resolution(self, x_resolution: u32, y_resolution: u32) -> Self {
+        let (point_size, _, _) = self.size;
+        Self {
+            size: (point_size, x_resolution, y_resolution),
+            ..self
+        }
+    }
+
+    /// Returns a copy with the font bounding box replaced.
+    pub fn with_bounding_box(self, bounding_box: BoundingBox) -> Self {
+        Self {
+            bounding_box,
+            ..self
+        }
+    }
+}
+
+/// Builder for [`Metadata`].
+///
+/// The version defaults to 2.1, all other values default to zero or an empty name.
+///
+/// [`Metadata`]: struct.Metadata.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataBuilder {
+    metadata: Metadata,
+}
+
+impl Default for MetadataBuilder {
+    fn default() -> Self {
+        Self {
+            metadata: Metadata {
+                version: 2.1,
+                name: String::new(),
+                size: (0, 0, 0),
+                bounding_box: (0, 0, 0, 0),
+            },
+        }
+    }
+}
+
+impl MetadataBuilder {
+    pub fn version(self, version: f32) -> Self {
+        Self {
+            metadata: self.metadata.with_version(version),
+        }
+    }
+
+    pub fn name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            metadata: self.metadata.with_name(name),
+        }
+    }
+
+    pub fn point_size(self, point_size: i32) -> Self {
+        Self {
+            metadata: self.metadata.with_point_size(point_size),
+        }
+    }
+
+    pub fn resolution(self, x_resolution: u32, y_resolution: u32) -> Self {
+        Self {
+            metadata: self.metadata.with_resolution(x_resolution, y_resolution),
+        }
+    }
+
+    pub fn bounding_box(self, bounding_box: BoundingBox) -> Self {
+        Self {
+            metadata: self.metadata.with_bounding_box(bounding_box),
+        }
+    }
+
+    pub fn build(self) -> Metadata {
+        self.metadata
+    }
+}
+
 named!(
     metadata_version<CompleteByteSlice, f32>,
     flat_map!(
@@ -45,6 +150,14 @@ named!(
     ))
 );
 
+named!(
+    pub size_variant_header<CompleteByteSlice, (FontSize, Option<BoundingBox>)>,
+    ws!(do_parse!(
+        size: metadata_size >> optional_comments >> bounding_box: opt!(metadata_bounding_box)
+            >> optional_comments >> ((size, bounding_box))
+    ))
+);
+
 named!(
     pub header<CompleteByteSlice, Metadata>,
     ws!(do_parse!(
@@ -80,4 +193,93 @@ mod tests {
             Ok((EMPTY, 2.1f32))
         );
     }
+
+    #[test]
+    fn it_parses_the_font_size() {
+        assert_eq!(
+            metadata_size(CompleteByteSlice(b"SIZE 16 75 75\n")),
+            Ok((EMPTY, (16, 75, 75)))
+        );
+
+        assert_eq!(
+            metadata_size(CompleteByteSlice(b"SIZE  16\t75   100 \n")),
+            Ok((EMPTY, (16, 75, 100)))
+        );
+    }
+
+    #[test]
+    fn it_parses_the_font_bounding_box() {
+        assert_eq!(
+            metadata_bounding_box(CompleteByteSlice(b"FONTBOUNDINGBOX 16 24 0 0\n")),
+            Ok((EMPTY, (16, 24, 0, 0)))
+        );
+
+        assert_eq!(
+            metadata_bounding_box(CompleteByteSlice(b"FONTBOUNDINGBOX  16  24  0  0\n")),
+            Ok((EMPTY, (16, 24, 0, 0)))
+        );
+
+        assert_eq!(
+            metadata_bounding_box(CompleteByteSlice(
+                b"FONTBOUNDINGBOX\t16 \t24   -1\t-4  \r\n"
+            )),
+            Ok((EMPTY, (16, 24, -1, -4)))
+        );
+    }
+
+    #[test]
+    fn it_parses_headers_with_extra_whitespace() {
+        let header_data = CompleteByteSlice(
+            b"STARTFONT  2.1\nFONT \"spaced\"\nSIZE  16  75  75\nFONTBOUNDINGBOX  16  24  0  -2\n",
+        );
+
+        assert_eq!(
+            header(header_data),
+            Ok((
+                EMPTY,
+                Metadata {
+                    version: 2.1,
+                    name: "\"spaced\"".to_string(),
+                    size: (16, 75, 75),
+                    bounding_box: (16, 24, 0, -2),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn it_builds_metadata() {
+        let metadata = Metadata::builder()
+            .name("\"built\"")
+            .point_size(8)
+            .resolution(75, 100)
+            .bounding_box((8, 10, 0, -2))
+            .build();
+
+        assert_eq!(
+            metadata,
+            Metadata {
+                version: 2.1,
+                name: "\"built\"".to_string(),
+                size: (8, 75, 100),
+                bounding_box: (8, 10, 0, -2),
+            }
+        );
+
+        let metadata = metadata
+            .with_version(2.2)
+            .with_name("other")
+            .with_resolution(96, 96)
+            .with_bounding_box((6, 8, 0, -1));
+
+        assert_eq!(
+            metadata,
+            Metadata {
+                version: 2.2,
+                name: "other".to_string(),
+                size: (8, 96, 96),
+                bounding_box: (6, 8, 0, -1),
+            }
+        );
+    }
 }
@@ -0,0 +1,364 @@
+use std::convert::TryFrom;
+
+use super::{BDFFont, BoundingBox, Glyph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaledFontMetrics {
+    /// Font size in pixels.
+    pub pixel_size: u32,
+    /// Distance from the baseline to the top of the font.
+    pub ascent: i32,
+    /// Distance from the baseline to the bottom of the font.
+    pub descent: i32,
+    /// Distance between the baselines of two lines.
+    pub line_height: u32,
+    /// Average advance width of all glyphs.
+    pub average_advance: u32,
+}
+
+/// Vertical metrics used to position text decorations and to align text.
+///
+/// All positions are the distance of the top edge from the baseline in pixels, with positive
+/// values above the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HintingMetrics {
+    /// Height of capital letters.
+    pub cap_height: Option<u32>,
+    /// Height of lowercase letters without ascenders.
+    pub x_height: Option<u32>,
+    /// Distance from the baseline to the top of the font.
+    pub ascender: i32,
+    /// Distance from the baseline to the bottom of the font.
+    pub descender: i32,
+    pub strikeout_position: i32,
+    pub strikeout_thickness: u32,
+    pub underline_position: i32,
+    pub underline_thickness: u32,
+}
+
+/// Everything needed to place a char, with the bearings and extents defined like in the X11
+/// `XCharStruct`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharInfo<'a> {
+    pub glyph: &'a Glyph,
+    /// Distance from the origin to the origin of the next char.
+    pub advance_width: u32,
+    /// Distance from the origin to the left edge of the glyph.
+    pub left_bearing: i32,
+    /// Distance from the origin to the right edge of the glyph.
+    pub right_bearing: i32,
+    /// Distance from the baseline to the top edge of the glyph.
+    pub ascent: i32,
+    /// Distance from the baseline to the bottom edge of the glyph, positive below the baseline.
+    pub descent: i32,
+}
+
+impl BDFFont {
+    /// Returns the ascent and descent from the `FONT_ASCENT` and `FONT_DESCENT` properties,
+    /// falling back to the font bounding box.
+    pub(crate) fn ascent_descent(&self) -> (i32, i32) {
+        let (_, height, _, y) = self
+            .metadata
+            .as_ref()
+            .map_or((0, 0, 0, 0), |metadata| metadata.bounding_box);
+
+        (
+            self.int_property("FONT_ASCENT")
+                .unwrap_or(height as i32 + y),
+            self.int_property("FONT_DESCENT").unwrap_or(-y),
+        )
+    }
+
+    /// Returns the glyph for `c` together with its placement metrics.
+    pub fn char_info(&self, c: char) -> Option<CharInfo<'_>> {
+        let glyph = self.glyph(c)?;
+        let (width, height, x_offset, y_offset) = glyph.bounding_box;
+
+        Some(CharInfo {
+            glyph,
+            advance_width: glyph.advance_width(),
+            left_bearing: x_offset,
+            right_bearing: x_offset + width as i32,
+            ascent: y_offset + height as i32,
+            descent: -y_offset,
+        })
+    }
+
+    /// Returns the smallest bounding box that contains all set pixels of the glyph for `c`.
+    ///
+    /// The bounding box uses the same format as the glyph bounding box, with the offsets
+    /// relative to the glyph origin. Returns `None` if the font has no glyph for `c` or the glyph
+    /// has no set pixels.
+    pub fn compute_ink_bounding_box(&self, c: char) -> Option<BoundingBox> {
+        let glyph = self.glyph(c)?;
+        let (left, top, width, height) = glyph.ink_bounds()?;
+        let (_, glyph_height, x_offset, y_offset) = glyph.bounding_box;
+
+        Some((
+            width,
+            height,
+            x_offset + left as i32,
+            y_offset + (glyph_height - top - height) as i32,
+        ))
+    }
+
+    /// Returns the height of the topmost set pixel of the glyph for `c` above the baseline.
+    fn ink_height(&self, c: char) -> Option<u32> {
+        let glyph = self.glyph(c)?;
+        let (_, top, _, _) = glyph.ink_bounds()?;
+        let (_, height, _, y_offset) = glyph.bounding_box;
+
+        u32::try_from(y_offset + (height - top) as i32).ok()
+    }
+
+    /// Computes the metrics needed to align text and draw text decorations.
+    ///
+    /// The cap height and x-height are taken from the `CAP_HEIGHT` and `X_HEIGHT` properties or
+    /// measured from the glyphs for `H` and `x`. Underline and strikeout use the XLFD
+    /// properties if present and otherwise default to lines below the baseline and at half the
+    /// x-height.
+    pub fn compute_hinting_metrics(&self) -> HintingMetrics {
+        let (ascender, descender) = self.ascent_descent();
+
+        let height_property = |name| {
+            self.int_property(name)
+                .and_then(|value| u32::try_from(value).ok())
+        };
+        let cap_height = height_property("CAP_HEIGHT").or_else(|| self.ink_height('H'));
+        let x_height = height_property("X_HEIGHT").or_else(|| self.ink_height('x'));
+
+        let default_thickness = ((ascender + descender) / 16).max(1) as u32;
+
+        let underline_thickness =
+            height_property("UNDERLINE_THICKNESS").unwrap_or(default_thickness);
+        let underline_position = match self.int_property("UNDERLINE_POSITION") {
+            Some(position) => -position,
+            None => -((descender.max(1) + 1) / 2),
+        };
+
+        let (strikeout_position, strikeout_thickness) = match (
+            self.int_property("STRIKEOUT_ASCENT"),
+            self.int_property("STRIKEOUT_DESCENT"),
+        ) {
+            (Some(ascent), Some(descent)) => (ascent, (ascent + descent).max(0) as u32),
+            _ => {
+                let x_height = x_height.map_or(ascender * 2 / 3, |x_height| x_height as i32);
+                ((x_height + default_thickness as i32) / 2, default_thickness)
+            }
+        };
+
+        HintingMetrics {
+            cap_height,
+            x_height,
+            ascender,
+            descender,
+            strikeout_position,
+            strikeout_thickness,
+            underline_position,
+            underline_thickness,
+        }
+    }
+
+    /// Scales the font metrics from the vertical resolution declared in `SIZE` to `target_dpi`.
+    ///
+    /// Fonts without a declared resolution aren't scaled.
+    pub fn metrics_at_dpi(&self, target_dpi: u32) -> ScaledFontMetrics {
+        let (point_size, resolution) = self
+            .metadata
+            .as_ref()
+            .map_or((0, 0), |metadata| (metadata.size.0, metadata.size.2));
+
+        let scale = if resolution == 0 {
+            1.0
+        } else {
+            f64::from(target_dpi) / f64::from(resolution)
+        };
+        let scaled = |value: f64| (value * scale).round();
+
+        let (ascent, descent) = self.ascent_descent();
+
+        let average_advance = if self.glyphs.is_empty() {
+            0.0
+        } else {
+            let total: u32 = self.glyphs.iter().map(|glyph| glyph.advance_width()).sum();
+            f64::from(total) / self.glyphs.len() as f64
+        };
+
+        ScaledFontMetrics {
+            pixel_size: scaled(f64::from(point_size.max(0)) * f64::from(resolution) / 72.0) as u32,
+            ascent: scaled(f64::from(ascent)) as i32,
+            descent: scaled(f64::from(descent)) as i32,
+            line_height: scaled(f64::from(ascent + descent)).max(0.0) as u32,
+            average_advance: scaled(average_advance) as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {BDFParser, PropertyValue};
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "metrics"
+SIZE 12 75 75
+FONTBOUNDINGBOX 8 12 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 10
+FONT_DESCENT 2
+ENDPROPERTIES
+STARTCHAR A
+ENCODING 65
+DWIDTH 6 0
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+STARTCHAR W
+ENCODING 87
+DWIDTH 8 0
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+    const HINTING_FONT: &str = r#"STARTFONT 2.1
+FONT "hinting"
+SIZE 12 75 75
+FONTBOUNDINGBOX 8 12 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 10
+FONT_DESCENT 2
+ENDPROPERTIES
+STARTCHAR H
+ENCODING 72
+DWIDTH 6 0
+BBX 5 7 0 0
+BITMAP
+88
+88
+88
+F8
+88
+88
+88
+ENDCHAR
+STARTCHAR x
+ENCODING 120
+DWIDTH 6 0
+BBX 5 5 0 0
+BITMAP
+00
+88
+50
+20
+D8
+ENDCHAR
+ENDFONT
+"#;
+
+    #[test]
+    fn it_scales_metrics_to_the_target_dpi() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+
+        assert_eq!(
+            font.metrics_at_dpi(75),
+            ScaledFontMetrics {
+                pixel_size: 13,
+                ascent: 10,
+                descent: 2,
+                line_height: 12,
+                average_advance: 7,
+            }
+        );
+
+        assert_eq!(
+            font.metrics_at_dpi(150),
+            ScaledFontMetrics {
+                pixel_size: 25,
+                ascent: 20,
+                descent: 4,
+                line_height: 24,
+                average_advance: 14,
+            }
+        );
+    }
+
+    #[test]
+    fn it_returns_char_info() {
+        let mut font = BDFParser::from_str(HINTING_FONT).parse().unwrap().1;
+        font.glyphs[1].bounding_box = (5, 7, 1, -2);
+
+        assert_eq!(
+            font.char_info('x'),
+            Some(CharInfo {
+                glyph: &font.glyphs[1],
+                advance_width: 6,
+                left_bearing: 1,
+                right_bearing: 6,
+                ascent: 5,
+                descent: 2,
+            })
+        );
+        assert_eq!(font.char_info('A'), None);
+    }
+
+    #[test]
+    fn it_computes_ink_bounding_boxes() {
+        let mut font = BDFParser::from_str(HINTING_FONT).parse().unwrap().1;
+        font.glyphs[1].bounding_box = (6, 5, 1, -2);
+
+        assert_eq!(font.compute_ink_bounding_box('H'), Some((5, 7, 0, 0)));
+        assert_eq!(font.compute_ink_bounding_box('x'), Some((5, 4, 1, -2)));
+        assert_eq!(font.compute_ink_bounding_box('A'), None);
+
+        font.glyphs[1].bitmap = vec![0; 2];
+        assert_eq!(font.compute_ink_bounding_box('x'), None);
+    }
+
+    #[test]
+    fn it_computes_hinting_metrics_from_glyphs() {
+        let font = BDFParser::from_str(HINTING_FONT).parse().unwrap().1;
+
+        assert_eq!(
+            font.compute_hinting_metrics(),
+            HintingMetrics {
+                cap_height: Some(7),
+                x_height: Some(4),
+                ascender: 10,
+                descender: 2,
+                strikeout_position: 2,
+                strikeout_thickness: 1,
+                underline_position: -1,
+                underline_thickness: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn it_computes_hinting_metrics_from_properties() {
+        let mut font = BDFParser::from_str(HINTING_FONT).parse().unwrap().1;
+        font.properties.as_mut().unwrap().extend(vec![
+            ("CAP_HEIGHT".into(), PropertyValue::Int(8)),
+            ("X_HEIGHT".into(), PropertyValue::Int(6)),
+            ("STRIKEOUT_ASCENT".into(), PropertyValue::Int(4)),
+            ("STRIKEOUT_DESCENT".into(), PropertyValue::Int(-2)),
+            ("UNDERLINE_POSITION".into(), PropertyValue::Int(2)),
+            ("UNDERLINE_THICKNESS".into(), PropertyValue::Int(2)),
+        ]);
+
+        assert_eq!(
+            font.compute_hinting_metrics(),
+            HintingMetrics {
+                cap_height: Some(8),
+                x_height: Some(6),
+                ascender: 10,
+                descender: 2,
+                strikeout_position: 4,
+                strikeout_thickness: 2,
+                underline_position: -2,
+                underline_thickness: 2,
+            }
+        );
+    }
+}
@@ -12,20 +12,35 @@ pub enum PropertyValue {
 
 pub type Properties = HashMap<String, PropertyValue>;
 
+/// Joins lines that end with a backslash with the following line and removes the indentation
+/// of tab-indented continuation lines.
+fn join_continued_lines(value: String) -> String {
+    value
+        .replace("\\\r\n", "")
+        .replace("\\\n", "")
+        .replace("\r\n\t", "\n")
+        .replace("\n\t", "\n")
+}
+
+// Quotes inside the value are escaped by doubling them.
 named!(property_value_string<CompleteByteSlice, PropertyValue>,
     map!(
         flat_map!(
-            ws!(delimited!(tag!("\""), take_until!("\""), tag!("\""))),
+            ws!(delimited!(
+                tag!("\""),
+                recognize!(many0!(alt_complete!(is_not!("\"") | tag!("\"\"")))),
+                tag!("\"")
+            )),
             parse_to!(String)
         ),
-        |str| PropertyValue::Text(str)
+        |value| PropertyValue::Text(join_continued_lines(value.replace("\"\"", "\"")))
     )
 );
 
 named!(property_value_int<CompleteByteSlice, PropertyValue>,
     ws!(map!(
         parse_to_i32,
-        |num| PropertyValue::Int(num)
+        PropertyValue::Int
     ))
 );
 
@@ -66,12 +81,69 @@ named!(
     )
 );
 
+// The keyword must be followed by whitespace, so that properties like `CHARSET_REGISTRY` don't
+// end the block.
+named!(property_block_end<CompleteByteSlice, CompleteByteSlice>,
+    preceded!(
+        opt!(multispace),
+        terminated!(
+            alt!(
+                tag!("STARTCHAR") |
+                tag!("ENDFONT") |
+                tag!("CHARS") |
+                tag!("NUMCHARS")
+            ),
+            not!(none_of!(" \t\r\n"))
+        )
+    )
+);
+
+// Parses a properties block without `ENDPROPERTIES`, which ends before the first line that
+// starts a glyph, the `CHARS` count or `ENDFONT`.
+named!(properties_unterminated<CompleteByteSlice, Properties>,
+    map!(
+        preceded!(num_properties, many0!(preceded!(not!(property_block_end), property))),
+        |res| res.into_iter().collect::<Properties>()
+    )
+);
+
+// Parses a properties block and returns `true` if the `ENDPROPERTIES` tag was missing.
+named!(
+    pub properties_recoverable<CompleteByteSlice, (Properties, bool)>,
+    alt_complete!(
+        map!(properties, |properties| (properties, false)) |
+        map!(properties_unterminated, |properties| (properties, true))
+    )
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const EMPTY: CompleteByteSlice = CompleteByteSlice(b"");
 
+    #[test]
+    fn it_parses_escaped_quotes() {
+        assert_eq!(
+            property(CompleteByteSlice(b"KEY \"a \"\"quoted\"\" word\"")),
+            Ok((
+                EMPTY,
+                (
+                    "KEY".to_string(),
+                    PropertyValue::Text("a \"quoted\" word".to_string())
+                )
+            ))
+        );
+
+        assert_eq!(
+            property(CompleteByteSlice(b"KEY \"\"")),
+            Ok((
+                EMPTY,
+                ("KEY".to_string(), PropertyValue::Text(String::new()))
+            ))
+        );
+    }
+
     #[test]
     fn it_parses_whitespacey_properties() {
         assert_eq!(
@@ -105,6 +177,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_joins_continued_string_properties() {
+        assert_eq!(
+            property(CompleteByteSlice(
+                b"COPYRIGHT \"This is a very long copyright \\\nnotice\""
+            )),
+            Ok((
+                EMPTY,
+                (
+                    "COPYRIGHT".to_string(),
+                    PropertyValue::Text("This is a very long copyright notice".to_string())
+                )
+            ))
+        );
+
+        assert_eq!(
+            property(CompleteByteSlice(b"NOTICE \"Windows \\\r\nline endings\"")),
+            Ok((
+                EMPTY,
+                (
+                    "NOTICE".to_string(),
+                    PropertyValue::Text("Windows line endings".to_string())
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn it_parses_tab_indented_string_properties() {
+        let input = "STARTPROPERTIES 2
+COPYRIGHT \"Copyright (c) 1999
+\tExample Foundry\"
+NOTICE \"Line one\r
+\tline two\r
+\tline three\"
+ENDPROPERTIES";
+
+        assert_eq!(
+            properties(CompleteByteSlice(input.as_bytes())),
+            Ok((
+                EMPTY,
+                hashmap! {
+                    "COPYRIGHT".into() => PropertyValue::Text("Copyright (c) 1999\nExample Foundry".into()),
+                    "NOTICE".into() => PropertyValue::Text("Line one\nline two\nline three".into()),
+                }
+            ))
+        );
+    }
+
     #[test]
     fn it_parses_integer_properties() {
         assert_eq!(
@@ -130,7 +251,7 @@ mod tests {
 ENDPROPERTIES"#;
 
         assert_eq!(
-            properties(CompleteByteSlice(&input.as_bytes())),
+            properties(CompleteByteSlice(input.as_bytes())),
             Ok((EMPTY, Properties::new()))
         );
     }
@@ -148,8 +269,58 @@ ENDPROPERTIES"#;
         ];
 
         assert_eq!(
-            properties(CompleteByteSlice(&input.as_bytes())),
+            properties(CompleteByteSlice(input.as_bytes())),
             Ok((EMPTY, expected))
         );
     }
+
+    #[test]
+    fn it_recovers_from_missing_endproperties() {
+        let input =
+            CompleteByteSlice(b"STARTPROPERTIES 2\nFONT_ASCENT 7\nFONT_DESCENT 1\nCHARS 1\n");
+
+        assert_eq!(
+            properties_recoverable(input),
+            Ok((
+                CompleteByteSlice(b"CHARS 1\n"),
+                (
+                    hashmap! {
+                        "FONT_ASCENT".into() => PropertyValue::Int(7),
+                        "FONT_DESCENT".into() => PropertyValue::Int(1),
+                    },
+                    true
+                )
+            ))
+        );
+        assert!(properties(input).is_err());
+
+        let input = CompleteByteSlice(
+            b"STARTPROPERTIES 2\nCHARSET_REGISTRY \"ISO10646\"\nCHARSET_ENCODING \"1\"\nENDFONT",
+        );
+        assert_eq!(
+            properties_recoverable(input),
+            Ok((
+                CompleteByteSlice(b"ENDFONT"),
+                (
+                    hashmap! {
+                        "CHARSET_REGISTRY".into() => PropertyValue::Text("ISO10646".into()),
+                        "CHARSET_ENCODING".into() => PropertyValue::Text("1".into()),
+                    },
+                    true
+                )
+            ))
+        );
+
+        let input = CompleteByteSlice(b"STARTPROPERTIES 1\nFONT_ASCENT 7\nENDPROPERTIES\n");
+        assert_eq!(
+            properties_recoverable(input),
+            Ok((
+                CompleteByteSlice(b"\n"),
+                (
+                    hashmap! { "FONT_ASCENT".into() => PropertyValue::Int(7) },
+                    false
+                )
+            ))
+        );
+    }
 }
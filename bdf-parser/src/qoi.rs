@@ -0,0 +1,172 @@
+use super::BDFFont;
+
+/// Number of glyphs per row in the glyph sheet.
+const COLUMNS: usize = 16;
+
+const QOI_OP_INDEX: u8 = 0x00;
+const QOI_OP_RUN: u8 = 0xc0;
+const QOI_OP_RGB: u8 = 0xfe;
+
+const BLACK: [u8; 3] = [0, 0, 0];
+const WHITE: [u8; 3] = [255, 255, 255];
+
+impl BDFFont {
+    /// Renders all glyphs into a grid and encodes it as a QOI image.
+    ///
+    /// Every glyph is drawn in a cell the size of the font bounding box, 16 cells per row, and
+    /// each pixel is scaled up to `scale` x `scale` pixels. Set pixels are black on a white
+    /// background.
+    pub fn to_qoi_glyph_sheet(&self, scale: u32) -> Vec<u8> {
+        let cell = self
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.bounding_box)
+            .unwrap_or((0, 0, 0, 0));
+        let (cell_width, cell_height, _, _) = cell;
+
+        let columns = self.glyphs.len().min(COLUMNS);
+        let rows = self.glyphs.len().div_ceil(COLUMNS);
+
+        let stride = (cell_width as usize).div_ceil(8);
+        let cells: Vec<Vec<u8>> = self
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.cell_bitmap(cell))
+            .collect();
+
+        let width = columns as u32 * cell_width * scale;
+        let height = rows as u32 * cell_height * scale;
+
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = ((x / scale) as usize, (y / scale) as usize);
+                let (column, cx) = (x / cell_width as usize, x % cell_width as usize);
+                let (row, cy) = (y / cell_height as usize, y % cell_height as usize);
+
+                let set = cells
+                    .get(row * COLUMNS + column)
+                    .is_some_and(|bitmap| bitmap[cy * stride + cx / 8] & (0x80 >> (cx % 8)) != 0);
+
+                if set {
+                    BLACK
+                } else {
+                    WHITE
+                }
+            });
+
+        encode(width, height, pixels)
+    }
+}
+
+/// Encodes RGB pixels as a QOI image.
+fn encode<I>(width: u32, height: u32, pixels: I) -> Vec<u8>
+where
+    I: Iterator<Item = [u8; 3]>,
+{
+    let mut out = b"qoif".to_vec();
+    out.extend_from_slice(&width.to_be_bytes());
+    out.extend_from_slice(&height.to_be_bytes());
+    out.extend_from_slice(&[3, 0]);
+
+    // The index is initialized with transparent black, which never matches an opaque pixel
+    let mut index = [[0u8; 4]; 64];
+    let mut previous = BLACK;
+    let mut run = 0;
+
+    for pixel in pixels {
+        if pixel == previous {
+            run += 1;
+            if run == 62 {
+                out.push(QOI_OP_RUN | (run - 1));
+                run = 0;
+            }
+            continue;
+        }
+
+        if run > 0 {
+            out.push(QOI_OP_RUN | (run - 1));
+            run = 0;
+        }
+
+        let [r, g, b] = pixel;
+        // The alpha channel is always 255
+        let hash = (r as usize * 3 + g as usize * 5 + b as usize * 7 + 255 * 11) % 64;
+
+        if index[hash] == [r, g, b, 255] {
+            out.push(QOI_OP_INDEX | hash as u8);
+        } else {
+            index[hash] = [r, g, b, 255];
+            out.extend_from_slice(&[QOI_OP_RGB, r, g, b]);
+        }
+
+        previous = pixel;
+    }
+
+    if run > 0 {
+        out.push(QOI_OP_RUN | (run - 1));
+    }
+
+    out.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    #[test]
+    fn it_encodes_runs_and_indexes() {
+        let pixels = vec![BLACK, BLACK, WHITE, WHITE, BLACK, BLACK, WHITE, WHITE];
+
+        assert_eq!(
+            encode(4, 2, pixels.into_iter()),
+            vec![
+                b'q', b'o', b'i', b'f', 0, 0, 0, 4, 0, 0, 0, 2, 3, 0,    // header
+                0xc1, // run of 2 black pixels
+                0xfe, 0xff, 0xff, 0xff, 0xc0, // white pixels
+                0xfe, 0x00, 0x00, 0x00, 0xc0, // black pixels
+                0x26, 0xc0, // white pixels from the index
+                0, 0, 0, 0, 0, 0, 0, 1, // end marker
+            ]
+        );
+    }
+
+    #[test]
+    fn it_splits_long_runs() {
+        let qoi = encode(100, 1, vec![BLACK; 100].into_iter());
+
+        assert_eq!(&qoi[14..16], &[0xc0 | 61, 0xc0 | 37]);
+    }
+
+    #[test]
+    fn it_exports_glyph_sheets() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "qoi"
+SIZE 2 75 75
+FONTBOUNDINGBOX 2 1 0 0
+STARTCHAR A
+ENCODING 65
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        let qoi = font.to_qoi_glyph_sheet(2);
+
+        assert_eq!(&qoi[4..12], &[0, 0, 0, 4, 0, 0, 0, 2]);
+        assert_eq!(
+            &qoi[14..qoi.len() - 8],
+            &[0xc1, 0xfe, 0xff, 0xff, 0xff, 0xc0, 0xfe, 0, 0, 0, 0xc0, 0x26, 0xc0]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use super::BDFFont;
+
+impl BDFFont {
+    /// Calls `f(x, y)` for every set pixel of the text, relative to the top left corner of the
+    /// text, and returns the size of the text.
+    ///
+    /// Lines are separated by `\n` and chars without a glyph are skipped.
+    pub(crate) fn layout_text<F>(&self, text: &str, mut f: F) -> (u32, u32)
+    where
+        F: FnMut(i32, i32),
+    {
+        let (ascent, descent) = self.ascent_descent();
+        let line_height = (ascent + descent).max(0) as u32;
+
+        let mut width = 0;
+        let mut lines = 0;
+
+        for (line, text) in text.split('\n').enumerate() {
+            let baseline = (line as u32 * line_height) as i32 + ascent;
+            let mut pen = 0;
+
+            for glyph in text.chars().filter_map(|c| self.glyph(c)) {
+                let (glyph_width, glyph_height, x_offset, y_offset) = glyph.bounding_box;
+                let top = baseline - y_offset - glyph_height as i32;
+
+                for gy in 0..glyph_height {
+                    for gx in 0..glyph_width {
+                        if glyph.get_pixel(gx, gy) == Some(true) {
+                            f(pen + x_offset + gx as i32, top + gy as i32);
+                        }
+                    }
+                }
+
+                pen += glyph.advance_width() as i32;
+            }
+
+            width = width.max(pen as u32);
+            lines += 1;
+        }
+
+        (width, lines * line_height)
+    }
+
+    /// Renders text into a buffer with `bytes_per_pixel` bytes per pixel and returns the
+    /// buffer, its width and its height.
+    ///
+    /// Set pixels are filled with `fg` and all other pixels with `bg`, which allows 8 bit
+    /// grayscale, RGB and RGBA output. For 1 bit output use a single byte per pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fg` or `bg` isn't `bytes_per_pixel` bytes long.
+    pub fn render_string_to_raw_bytes(
+        &self,
+        text: &str,
+        bytes_per_pixel: u32,
+        fg: &[u8],
+        bg: &[u8],
+    ) -> (Vec<u8>, u32, u32) {
+        let bpp = bytes_per_pixel as usize;
+        assert_eq!(fg.len(), bpp, "fg must be bytes_per_pixel bytes long");
+        assert_eq!(bg.len(), bpp, "bg must be bytes_per_pixel bytes long");
+
+        let (width, height) = self.layout_text(text, |_, _| {});
+
+        let mut buffer = bg.repeat(width as usize * height as usize);
+
+        self.layout_text(text, |x, y| {
+            if x >= 0 && y >= 0 && (x as u32) < width && (y as u32) < height {
+                let offset = (y as usize * width as usize + x as usize) * bpp;
+                buffer[offset..offset + bpp].copy_from_slice(fg);
+            }
+        });
+
+        (buffer, width, height)
+    }
+
+    /// Converts every encoded glyph into an RGB bitmap with 3 bytes per pixel.
+    ///
+    /// Set pixels are filled with `fg` and all other pixels with `bg`. Each bitmap covers the
+    /// glyph bounding box, with every pixel scaled up to `scale` x `scale` pixels, so it is
+    /// `width * scale` pixels wide and `height * scale` pixels high.
+    pub fn to_colored_glyph_sheets(
+        &self,
+        fg: [u8; 3],
+        bg: [u8; 3],
+        scale: u32,
+    ) -> Vec<(char, Vec<u8>)> {
+        self.glyphs
+            .iter()
+            .filter_map(|glyph| {
+                let c = glyph.encoding()?;
+                let (width, height, _, _) = glyph.bounding_box;
+
+                let bitmap = (0..height * scale)
+                    .flat_map(|y| (0..width * scale).map(move |x| (x, y)))
+                    .flat_map(|(x, y)| {
+                        if glyph.get_pixel(x / scale, y / scale) == Some(true) {
+                            fg
+                        } else {
+                            bg
+                        }
+                    })
+                    .collect();
+
+                Some((c, bitmap))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "render"
+SIZE 3 75 75
+FONTBOUNDINGBOX 2 3 0 -1
+STARTPROPERTIES 2
+FONT_ASCENT 2
+FONT_DESCENT 1
+ENDPROPERTIES
+STARTCHAR i
+ENCODING 105
+DWIDTH 2 0
+BBX 1 2 0 0
+BITMAP
+80
+80
+ENDCHAR
+STARTCHAR comma
+ENCODING 44
+DWIDTH 2 0
+BBX 1 2 0 -1
+BITMAP
+80
+80
+ENDCHAR
+ENDFONT
+"#;
+
+    fn font() -> BDFFont {
+        BDFParser::from_str(FONT).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_lays_out_text() {
+        let mut pixels = Vec::new();
+        let size = font().layout_text("i,\ni", |x, y| pixels.push((x, y)));
+
+        assert_eq!(size, (4, 6));
+        assert_eq!(pixels, vec![(0, 0), (0, 1), (2, 1), (2, 2), (0, 3), (0, 4)]);
+    }
+
+    #[test]
+    fn it_renders_raw_bytes() {
+        assert_eq!(
+            font().render_string_to_raw_bytes("i,", 1, &[0xff], &[0x00]),
+            (
+                vec![
+                    0xff, 0x00, 0x00, 0x00, //
+                    0xff, 0x00, 0xff, 0x00, //
+                    0x00, 0x00, 0xff, 0x00, //
+                ],
+                4,
+                3
+            )
+        );
+
+        let (buffer, width, height) =
+            font().render_string_to_raw_bytes("i", 3, &[1, 2, 3], &[4, 5, 6]);
+        assert_eq!((width, height), (2, 3));
+        assert_eq!(&buffer[0..6], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn it_converts_glyphs_to_colored_bitmaps() {
+        const FG: [u8; 3] = [1, 2, 3];
+        const BG: [u8; 3] = [4, 5, 6];
+
+        let mut font = font();
+        font.glyphs[0].bounding_box = (2, 1, 0, 0);
+        font.glyphs[1].charcode = -1;
+
+        assert_eq!(
+            font.to_colored_glyph_sheets(FG, BG, 1),
+            vec![('i', [FG, BG].concat())]
+        );
+        assert_eq!(
+            font.to_colored_glyph_sheets(FG, BG, 2),
+            vec![('i', [FG, FG, BG, BG, FG, FG, BG, BG].concat())]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use super::BDFFont;
+
+impl BDFFont {
+    /// Generates Rust source code for `include!` from a build script.
+    ///
+    /// The generated code defines two constants at the top level, so that it can be included
+    /// into any module: `FONT_DATA` contains the bitmaps of all encoded glyphs with each row
+    /// padded to a whole byte, and `GLYPH_INDEX` contains one `(codepoint, bitmap_offset, width,
+    /// height, advance, x_offset, y_offset)` entry per glyph, sorted by codepoint. `identifier`
+    /// is only used to name the font in the header comment.
+    pub fn to_embedded_rust_include(&self, identifier: &str) -> String {
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            "// Generated from the BDF font {}, don't edit.",
+            identifier
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "pub const FONT_DATA: &[u8] = &[").unwrap();
+        for row in self.bitmaps_contiguous().chunks(16) {
+            let row: Vec<String> = row.iter().map(|byte| format!("0x{:02x}", byte)).collect();
+            writeln!(out, "    {},", row.join(", ")).unwrap();
+        }
+        writeln!(out, "];").unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(
+            out,
+            "pub const GLYPH_INDEX: &[(u32, u32, u32, u32, u32, i32, i32)] = &["
+        )
+        .unwrap();
+        for (codepoint, descriptor) in self.to_lookup_table() {
+            writeln!(
+                out,
+                "    (0x{:04x}, {}, {}, {}, {}, {}, {}),",
+                codepoint,
+                descriptor.bitmap_offset,
+                descriptor.width,
+                descriptor.height,
+                descriptor.advance,
+                descriptor.x_offset,
+                descriptor.y_offset
+            )
+            .unwrap();
+        }
+        writeln!(out, "];").unwrap();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use BDFParser;
+
+    #[test]
+    fn it_generates_rust_source() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "include"
+SIZE 8 75 75
+FONTBOUNDINGBOX 12 2 0 -1
+STARTCHAR B
+ENCODING 66
+DWIDTH 3 0
+BBX 2 1 0 0
+BITMAP
+C0
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+DWIDTH 13 0
+BBX 12 2 0 -1
+BITMAP
+FFF0
+8010
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.to_embedded_rust_include("font_8x2"),
+            r#"// Generated from the BDF font font_8x2, don't edit.
+
+#[allow(dead_code)]
+pub const FONT_DATA: &[u8] = &[
+    0xff, 0xf0, 0x80, 0x10, 0xc0,
+];
+
+#[allow(dead_code)]
+pub const GLYPH_INDEX: &[(u32, u32, u32, u32, u32, i32, i32)] = &[
+    (0x0041, 0, 12, 2, 13, 0, -1),
+    (0x0042, 4, 2, 1, 3, 0, 0),
+];
+"#
+        );
+    }
+}
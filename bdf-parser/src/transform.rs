@@ -0,0 +1,550 @@
+use std::collections::HashMap;
+
+use super::glyph::pack_pixels;
+use super::{BDFFont, PropertyValue};
+
+impl BDFFont {
+    /// Removes empty rows and columns around the set pixels of every glyph.
+    ///
+    /// The bounding box offsets are adjusted so that the remaining pixels keep their position.
+    /// Glyphs without any set pixels are reduced to an empty bounding box.
+    pub fn trim_whitespace_margins(&mut self) {
+        for glyph in &mut self.glyphs {
+            match glyph.ink_bounds() {
+                Some((left, top, width, height)) => glyph.crop(left, top, width, height),
+                None => {
+                    glyph.bounding_box.0 = 0;
+                    glyph.bounding_box.1 = 0;
+                    glyph.bitmap.clear();
+                    glyph.bitmap_rows = 0;
+                }
+            }
+        }
+    }
+
+    /// Sets the device width of all glyphs without a `DWIDTH` to their bounding box width.
+    pub fn normalize_device_width(&mut self) {
+        for glyph in &mut self.glyphs {
+            if glyph.device_width.is_none() {
+                glyph.device_width = Some((glyph.bounding_box.0, 0));
+            }
+        }
+    }
+
+    /// Returns the font without its properties.
+    pub fn drop_properties(self) -> BDFFont {
+        BDFFont {
+            properties: None,
+            ..self
+        }
+    }
+
+    /// Returns the font without its metadata.
+    pub fn drop_metadata(self) -> BDFFont {
+        BDFFont {
+            metadata: None,
+            ..self
+        }
+    }
+
+    /// Returns a copy of this font with the properties of both fonts.
+    ///
+    /// Properties from `other` take precedence if both fonts define the same property.
+    pub fn merge_properties(&self, other: &BDFFont) -> BDFFont {
+        let properties = match (&self.properties, &other.properties) {
+            (Some(properties), Some(other)) => {
+                let mut properties = properties.clone();
+                properties.extend(other.iter().map(|(k, v)| (k.clone(), v.clone())));
+                Some(properties)
+            }
+            (properties, other) => other.clone().or_else(|| properties.clone()),
+        };
+
+        BDFFont {
+            properties,
+            ..self.clone()
+        }
+    }
+
+    /// Creates a proportional variant of a monospaced font.
+    ///
+    /// Empty columns are trimmed from both sides of every glyph, the glyph is moved to the
+    /// origin and the advance width is set to the remaining width plus `letter_spacing`. Glyphs
+    /// without any set pixels, like spaces, keep their advance width.
+    pub fn make_proportional(&self, letter_spacing: u32) -> BDFFont {
+        let mut font = self.clone();
+
+        for glyph in &mut font.glyphs {
+            if let Some((left, _, width, _)) = glyph.ink_bounds() {
+                let height = glyph.bounding_box.1;
+
+                glyph.crop(left, 0, width, height);
+                glyph.bounding_box.2 = 0;
+                glyph.device_width = Some((width + letter_spacing, 0));
+            }
+        }
+
+        font
+    }
+
+    /// Creates an outlined variant of the font.
+    ///
+    /// Every glyph is dilated by `thickness` pixels and the original pixels are removed, which
+    /// leaves only the outline. The bounding boxes grow by `thickness` on every side, so that the
+    /// outline surrounds the original pixels, and the advance widths grow by twice the
+    /// thickness to keep the outlines from overlapping. Sizes that don't fit into their types
+    /// saturate.
+    pub fn outline_glyphs(&self, thickness: u32) -> BDFFont {
+        let mut font = self.clone();
+        let t = thickness.min(i32::MAX as u32) as i32;
+        let grow = |size: u32| size.saturating_add(thickness.saturating_mul(2));
+
+        for glyph in &mut font.glyphs {
+            let (width, height, x_offset, y_offset) = glyph.bounding_box;
+            let (new_width, new_height) = (grow(width), grow(height));
+
+            let original = |x: i64, y: i64| {
+                x >= 0 && y >= 0 && glyph.get_pixel(x as u32, y as u32) == Some(true)
+            };
+
+            let bitmap = pack_pixels(new_width, new_height, |x, y| {
+                let t = i64::from(t);
+                let (x, y) = (i64::from(x) - t, i64::from(y) - t);
+                let dilated = (y - t..=y + t).any(|dy| (x - t..=x + t).any(|dx| original(dx, dy)));
+
+                dilated != original(x, y)
+            });
+
+            glyph.bitmap = bitmap;
+            glyph.bitmap_rows = new_height;
+            glyph.bounding_box = (
+                new_width,
+                new_height,
+                x_offset.saturating_sub(t),
+                y_offset.saturating_sub(t),
+            );
+            glyph.device_width = Some((grow(glyph.advance_width()), 0));
+        }
+
+        if let Some(ref mut metadata) = font.metadata {
+            let (width, height, x, y) = metadata.bounding_box;
+            metadata.bounding_box = (
+                grow(width),
+                grow(height),
+                x.saturating_sub(t),
+                y.saturating_sub(t),
+            );
+        }
+
+        font
+    }
+
+    /// Removes encoded glyphs that look identical to an earlier glyph.
+    ///
+    /// Glyphs are identical if they have the same bitmap, bounding box and advance width. The
+    /// first glyph of each group is kept and every removed char is returned together with the
+    /// char of the glyph that replaces it. Unencoded glyphs are never removed.
+    pub fn deduplicate_by_bitmap(self) -> (BDFFont, Vec<(char, char)>) {
+        let mut representatives = HashMap::new();
+        let mut removed = Vec::new();
+
+        let glyphs = self
+            .glyphs
+            .into_iter()
+            .filter(|glyph| {
+                let c = match glyph.encoding() {
+                    Some(c) => c,
+                    None => return true,
+                };
+
+                let key = (
+                    glyph.bounding_box,
+                    glyph.advance_width(),
+                    glyph.bitmap.clone(),
+                );
+
+                match representatives.get(&key) {
+                    Some(&representative) => {
+                        removed.push((c, representative));
+                        false
+                    }
+                    None => {
+                        representatives.insert(key, c);
+                        true
+                    }
+                }
+            })
+            .collect();
+
+        (BDFFont { glyphs, ..self }, removed)
+    }
+
+    /// Returns a version of the font that fits on a display with the given size.
+    ///
+    /// If every glyph bounding box fits on the display the font is returned unchanged.
+    /// Otherwise the font is scaled down by an integer factor, which is computed from the glyph
+    /// bounding boxes so that every glyph fits, independent of the alignment of its pixels to
+    /// the scaled grid. A pixel in the scaled font is set if any pixel in the corresponding
+    /// block of the original font was set, which keeps thin strokes visible. Returns `None` if
+    /// the glyphs don't fit at any scale.
+    pub fn for_display(&self, display_width: u32, display_height: u32) -> Option<BDFFont> {
+        let fits = self.glyphs.iter().all(|glyph| {
+            let (width, height, _, _) = glyph.bounding_box;
+            width <= display_width && height <= display_height
+        });
+
+        if fits {
+            return Some(self.clone());
+        }
+
+        let mut factor = 2;
+        for glyph in &self.glyphs {
+            let (width, height, x, y) = glyph.bounding_box;
+            factor = factor
+                .max(fit_factor(x, width, display_width)?)
+                .max(fit_factor(y, height, display_height)?);
+        }
+
+        Some(self.scale_down(factor))
+    }
+
+    /// Scales the font down by `factor`, aligning the scaled pixels to the glyph origins.
+    fn scale_down(&self, factor: i32) -> BDFFont {
+        let scale_length = |length: u32| (length + factor as u32 / 2) / factor as u32;
+
+        let mut font = self.clone();
+
+        for glyph in &mut font.glyphs {
+            let original = glyph.clone();
+            let (width, height, x_offset, y_offset) = original.bounding_box;
+            let (new_x_offset, new_width) = scale_range(x_offset, width, factor);
+            let (new_y_offset, new_height) = scale_range(y_offset, height, factor);
+            let top = new_y_offset + new_height as i32 - 1;
+
+            glyph.bitmap = pack_pixels(new_width, new_height, |x, y| {
+                let left = (new_x_offset + x as i32) * factor - x_offset;
+                let bottom = (top - y as i32) * factor - y_offset;
+
+                (0..factor).any(|dy| {
+                    let row = height as i32 - 1 - (bottom + dy);
+                    (0..factor).any(|dx| {
+                        let column = left + dx;
+                        column >= 0
+                            && row >= 0
+                            && original.get_pixel(column as u32, row as u32) == Some(true)
+                    })
+                })
+            });
+            glyph.bounding_box = (new_width, new_height, new_x_offset, new_y_offset);
+            glyph.bitmap_rows = new_height;
+            glyph.device_width = original
+                .device_width
+                .map(|(x, y)| (scale_length(x), scale_length(y)));
+        }
+
+        if let Some(ref mut metadata) = font.metadata {
+            let (width, height, x, y) = metadata.bounding_box;
+            let (x, width) = scale_range(x, width, factor);
+            let (y, height) = scale_range(y, height, factor);
+            metadata.bounding_box = (width, height, x, y);
+
+            let (point_size, x_resolution, y_resolution) = metadata.size;
+            metadata.size = (
+                scale_length(point_size.max(0) as u32) as i32,
+                x_resolution,
+                y_resolution,
+            );
+        }
+
+        if let Some(ref mut properties) = font.properties {
+            for name in &["FONT_ASCENT", "FONT_DESCENT"] {
+                if let Some(PropertyValue::Int(value)) = properties.get_mut(*name) {
+                    *value = (*value + factor - 1).div_euclid(factor);
+                }
+            }
+        }
+
+        font
+    }
+}
+
+/// Returns a factor that scales the range of `size` pixels starting at `offset` down to at
+/// most `display_size` pixels, or `None` if no factor does.
+fn fit_factor(offset: i32, size: u32, display_size: u32) -> Option<i32> {
+    let end = offset + size as i32 - 1;
+
+    let factor = match display_size {
+        _ if size <= display_size => 1,
+        0 => return None,
+        // All pixels need to end up in the same block, which isn't possible if the range
+        // contains both sides of the origin
+        1 if offset >= 0 => end + 1,
+        1 if end < 0 => -offset,
+        1 => return None,
+        // A range of `size` pixels covers at most `ceil((size - 1) / factor) + 1` blocks
+        _ => (size - 1).div_ceil(display_size - 1).min(i32::MAX as u32) as i32,
+    };
+
+    Some(factor)
+}
+
+/// Scales the range of `size` pixels starting at `offset` down by `factor`.
+///
+/// Returns the offset and size of the scaled range.
+fn scale_range(offset: i32, size: u32, factor: i32) -> (i32, u32) {
+    let start = offset.div_euclid(factor);
+    if size == 0 {
+        return (start, 0);
+    }
+    let end = (offset + size as i32 - 1).div_euclid(factor);
+
+    (start, (end - start + 1) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {BDFParser, PropertyValue};
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "transform"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 4 0 -1
+STARTCHAR padded
+ENCODING 65
+DWIDTH 8 0
+BBX 8 4 0 -1
+BITMAP
+00
+18
+10
+00
+ENDCHAR
+STARTCHAR space
+ENCODING 32
+DWIDTH 8 0
+BBX 8 4 0 -1
+BITMAP
+00
+00
+00
+00
+ENDCHAR
+ENDFONT
+"#;
+
+    fn font() -> BDFFont {
+        BDFParser::from_str(FONT).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_trims_whitespace_margins() {
+        let mut font = font();
+        font.trim_whitespace_margins();
+
+        let glyph = font.glyph('A').unwrap();
+        assert_eq!(glyph.bounding_box, (2, 2, 3, 0));
+        assert_eq!(glyph.bitmap_bytes(), vec![0xc0, 0x80]);
+
+        let space = font.glyph(' ').unwrap();
+        assert_eq!(space.bounding_box, (0, 0, 0, -1));
+        assert!(space.bitmap.is_empty());
+    }
+
+    #[test]
+    fn it_normalizes_device_widths() {
+        let mut font = font();
+        font.glyphs[0].device_width = None;
+        font.glyphs[0].bounding_box.0 = 6;
+
+        font.normalize_device_width();
+
+        assert_eq!(font.glyphs[0].device_width, Some((6, 0)));
+        assert_eq!(font.glyphs[1].device_width, Some((8, 0)));
+    }
+
+    #[test]
+    fn it_drops_properties_and_metadata() {
+        let font = BDFFont {
+            properties: Some(hashmap! {
+                "FONT_ASCENT".into() => PropertyValue::Int(3),
+            }),
+            ..font()
+        };
+
+        let without_properties = font.clone().drop_properties();
+        assert_eq!(without_properties.properties, None);
+        assert_eq!(without_properties.metadata, font.metadata);
+        assert_eq!(without_properties.glyphs, font.glyphs);
+        assert_eq!(without_properties.chars, font.chars);
+        assert_eq!(without_properties.endfont, font.endfont);
+
+        let without_metadata = font.clone().drop_metadata();
+        assert_eq!(without_metadata.metadata, None);
+        assert_eq!(without_metadata.properties, font.properties);
+        assert_eq!(without_metadata.glyphs, font.glyphs);
+        assert_eq!(without_metadata.chars, font.chars);
+        assert_eq!(without_metadata.endfont, font.endfont);
+
+        let stripped = font.clone().drop_properties().drop_metadata();
+        assert_eq!(stripped.glyphs.len(), 2);
+        assert_eq!(stripped.glyph('A'), font.glyph('A'));
+    }
+
+    #[test]
+    fn it_merges_properties() {
+        let with_properties = |properties| BDFFont {
+            properties,
+            ..font()
+        };
+
+        let font = with_properties(Some(hashmap! {
+            "FOUNDRY".into() => PropertyValue::Text("A".into()),
+            "FONT_ASCENT".into() => PropertyValue::Int(3),
+        }));
+        let other = with_properties(Some(hashmap! {
+            "FOUNDRY".into() => PropertyValue::Text("B".into()),
+            "FONT_DESCENT".into() => PropertyValue::Int(1),
+        }));
+
+        let merged = font.merge_properties(&other);
+
+        assert_eq!(merged.glyphs, font.glyphs);
+        assert_eq!(
+            merged.properties,
+            Some(hashmap! {
+                "FOUNDRY".into() => PropertyValue::Text("B".into()),
+                "FONT_ASCENT".into() => PropertyValue::Int(3),
+                "FONT_DESCENT".into() => PropertyValue::Int(1),
+            })
+        );
+
+        assert_eq!(
+            with_properties(None).merge_properties(&font).properties,
+            font.properties
+        );
+        assert_eq!(
+            font.merge_properties(&with_properties(None)).properties,
+            font.properties
+        );
+    }
+
+    #[test]
+    fn it_makes_fonts_proportional() {
+        let font = font().make_proportional(1);
+
+        let glyph = font.glyph('A').unwrap();
+        assert_eq!(glyph.bounding_box, (2, 4, 0, -1));
+        assert_eq!(glyph.device_width, Some((3, 0)));
+        assert_eq!(glyph.bitmap_bytes(), vec![0x00, 0xc0, 0x80, 0x00]);
+
+        let space = font.glyph(' ').unwrap();
+        assert_eq!(space.bounding_box, (8, 4, 0, -1));
+        assert_eq!(space.device_width, Some((8, 0)));
+    }
+
+    #[test]
+    fn it_outlines_glyphs() {
+        let font = font().outline_glyphs(1);
+
+        let glyph = font.glyph('A').unwrap();
+        assert_eq!(glyph.bounding_box, (10, 6, -1, -2));
+        assert_eq!(glyph.device_width, Some((10, 0)));
+        assert_eq!(
+            glyph.bitmap_bytes(),
+            vec![
+                0x00, 0x00, // 0000000000
+                0x1e, 0x00, // 0001111000
+                0x12, 0x00, // 0001001000
+                0x16, 0x00, // 0001011000
+                0x1c, 0x00, // 0001110000
+                0x00, 0x00, // 0000000000
+            ]
+        );
+    }
+
+    #[test]
+    fn it_saturates_outlines_that_overflow() {
+        let font = BDFFont {
+            glyphs: Vec::new(),
+            ..font()
+        }
+        .outline_glyphs(u32::MAX / 2);
+
+        let metadata = font.metadata.unwrap();
+        assert_eq!(
+            metadata.bounding_box,
+            (u32::MAX, u32::MAX, -i32::MAX, i32::MIN)
+        );
+    }
+
+    #[test]
+    fn it_deduplicates_glyphs_by_bitmap() {
+        let mut font = font();
+        let mut duplicate = font.glyphs[0].clone();
+        duplicate.charcode = 0x391;
+        let mut unencoded = font.glyphs[1].clone();
+        unencoded.charcode = -1;
+        let mut nbsp = font.glyphs[1].clone();
+        nbsp.charcode = 0xa0;
+        font.glyphs.extend(vec![duplicate, unencoded, nbsp]);
+
+        let (font, removed) = font.deduplicate_by_bitmap();
+
+        assert_eq!(removed, vec![('\u{391}', 'A'), ('\u{a0}', ' ')]);
+        assert_eq!(
+            font.glyphs
+                .iter()
+                .map(|glyph| glyph.charcode)
+                .collect::<Vec<_>>(),
+            vec![65, 32, -1]
+        );
+    }
+
+    #[test]
+    fn it_fits_fonts_on_displays() {
+        let font = BDFFont {
+            properties: Some(hashmap! {
+                "FONT_ASCENT".into() => PropertyValue::Int(3),
+                "FONT_DESCENT".into() => PropertyValue::Int(1),
+            }),
+            ..font()
+        };
+
+        assert_eq!(font.for_display(8, 4), Some(font.clone()));
+        assert_eq!(font.for_display(0, 4), None);
+        assert_eq!(font.for_display(8, 1), None);
+
+        let scaled = font.for_display(5, 4).unwrap();
+        let glyph = scaled.glyph('A').unwrap();
+        assert_eq!(glyph.bounding_box, (4, 3, 0, -1));
+        assert_eq!(glyph.device_width, Some((4, 0)));
+        assert_eq!(glyph.bitmap_bytes(), vec![0x00, 0x60, 0x00]);
+        assert_eq!(scaled.glyph(' ').unwrap().bitmap_bytes(), vec![0x00; 3]);
+        assert_eq!(scaled.int_property("FONT_ASCENT"), Some(2));
+        assert_eq!(scaled.int_property("FONT_DESCENT"), Some(1));
+
+        let metadata = scaled.metadata.unwrap();
+        assert_eq!(metadata.bounding_box, (4, 3, 0, -1));
+        assert_eq!(metadata.size, (4, 75, 75));
+
+        let glyph = font.for_display(3, 3).unwrap().glyph('A').unwrap().clone();
+        assert_eq!(glyph.bounding_box, (2, 2, 0, -1));
+        assert_eq!(glyph.bitmap_bytes(), vec![0xc0, 0x00]);
+    }
+
+    #[test]
+    fn it_computes_factors_that_fit_every_alignment() {
+        assert_eq!(fit_factor(0, 8, 8), Some(1));
+        assert_eq!(fit_factor(0, 8, 0), None);
+        assert_eq!(fit_factor(2, 3, 1), Some(5));
+        assert_eq!(fit_factor(-3, 3, 1), Some(3));
+        assert_eq!(fit_factor(-1, 2, 1), None);
+        assert_eq!(fit_factor(-1, 8, 3), Some(4));
+
+        for &(offset, size, display_size) in &[(2, 3, 1), (-3, 3, 1), (-1, 8, 3), (5, 100, 7)] {
+            let factor = fit_factor(offset, size, display_size).unwrap();
+            assert!(scale_range(offset, size, factor).1 <= display_size);
+        }
+    }
+}
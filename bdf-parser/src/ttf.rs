@@ -0,0 +1,236 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use super::BDFFont;
+
+/// Index subtable format with variable metrics and 32 bit offsets.
+const INDEX_FORMAT: u16 = 1;
+/// Image format with small metrics and byte aligned bitmaps.
+const IMAGE_FORMAT: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TtfExportError {
+    /// The font has no metadata to take the size from.
+    MissingMetadata,
+    /// The font doesn't contain any glyphs.
+    NoGlyphs,
+    /// Bitmap tables are limited to 65536 glyphs.
+    TooManyGlyphs,
+    /// The metrics of the named glyph, or of the whole strike if the name is empty, are out of
+    /// range for the bitmap tables.
+    MetricsOutOfRange(String),
+}
+
+impl fmt::Display for TtfExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TtfExportError::MissingMetadata => write!(f, "font has no metadata"),
+            TtfExportError::NoGlyphs => write!(f, "font has no glyphs"),
+            TtfExportError::TooManyGlyphs => write!(f, "font has more than 65536 glyphs"),
+            TtfExportError::MetricsOutOfRange(name) => {
+                write!(f, "metrics of glyph {:?} are out of range", name)
+            }
+        }
+    }
+}
+
+impl Error for TtfExportError {}
+
+impl BDFFont {
+    /// Converts the font into the `EBDT` and `EBLC` tables of an OpenType font, which store the
+    /// glyphs as a single embedded bitmap strike.
+    ///
+    /// Glyph IDs are assigned in the order of the glyphs in the BDF font, so the glyph order of
+    /// the OpenType font must match it.
+    pub fn to_truetype_bitmap_tables(&self) -> Result<(Vec<u8>, Vec<u8>), TtfExportError> {
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or(TtfExportError::MissingMetadata)?;
+
+        if self.glyphs.is_empty() {
+            return Err(TtfExportError::NoGlyphs);
+        }
+        if self.glyphs.len() > 0x1_0000 {
+            return Err(TtfExportError::TooManyGlyphs);
+        }
+
+        let mut ebdt = vec![0, 2, 0, 0];
+        let mut offsets = Vec::with_capacity(self.glyphs.len() + 1);
+
+        for glyph in &self.glyphs {
+            let (width, height, x_offset, y_offset) = glyph.bounding_box;
+            let out_of_range = || TtfExportError::MetricsOutOfRange(glyph.name.clone());
+
+            offsets.push(ebdt.len() - 4);
+
+            ebdt.push(u8_metric(height as i32).ok_or_else(out_of_range)?);
+            ebdt.push(u8_metric(width as i32).ok_or_else(out_of_range)?);
+            ebdt.push(i8_metric(x_offset).ok_or_else(out_of_range)?);
+            ebdt.push(i8_metric(y_offset + height as i32).ok_or_else(out_of_range)?);
+            ebdt.push(u8_metric(glyph.advance_width() as i32).ok_or_else(out_of_range)?);
+            ebdt.extend(glyph.bitmap_bytes());
+        }
+        offsets.push(ebdt.len() - 4);
+
+        let line_metrics = self.sbit_line_metrics()?;
+        let pixel_size = self.metrics_at_dpi(metadata.size.2).pixel_size.min(0xff) as u8;
+        let last_glyph = (self.glyphs.len() - 1) as u16;
+
+        let index_subtable_array_offset = 8 + 48;
+        let index_tables_size = 8 + 8 + offsets.len() * 4;
+
+        let mut eblc = vec![0, 2, 0, 0];
+        eblc.extend_from_slice(&1u32.to_be_bytes());
+
+        // BitmapSize record
+        eblc.extend_from_slice(&(index_subtable_array_offset as u32).to_be_bytes());
+        eblc.extend_from_slice(&(index_tables_size as u32).to_be_bytes());
+        eblc.extend_from_slice(&1u32.to_be_bytes());
+        eblc.extend_from_slice(&0u32.to_be_bytes());
+        eblc.extend_from_slice(&line_metrics);
+        eblc.extend_from_slice(&line_metrics);
+        eblc.extend_from_slice(&0u16.to_be_bytes());
+        eblc.extend_from_slice(&last_glyph.to_be_bytes());
+        eblc.extend_from_slice(&[pixel_size, pixel_size, 1, 1]);
+
+        // IndexSubTableArray
+        eblc.extend_from_slice(&0u16.to_be_bytes());
+        eblc.extend_from_slice(&last_glyph.to_be_bytes());
+        eblc.extend_from_slice(&8u32.to_be_bytes());
+
+        // IndexSubTable
+        eblc.extend_from_slice(&INDEX_FORMAT.to_be_bytes());
+        eblc.extend_from_slice(&IMAGE_FORMAT.to_be_bytes());
+        eblc.extend_from_slice(&4u32.to_be_bytes());
+        for offset in offsets {
+            eblc.extend_from_slice(&(offset as u32).to_be_bytes());
+        }
+
+        Ok((ebdt, eblc))
+    }
+
+    /// Returns the horizontal `SbitLineMetrics` record of the strike.
+    fn sbit_line_metrics(&self) -> Result<[u8; 12], TtfExportError> {
+        let out_of_range = || TtfExportError::MetricsOutOfRange(String::new());
+        let (ascent, descent) = self.ascent_descent();
+
+        let glyphs = || self.glyphs.iter();
+        let min = |values: Vec<i32>| values.into_iter().min().unwrap_or(0);
+        let max = |values: Vec<i32>| values.into_iter().max().unwrap_or(0);
+
+        let width_max = max(glyphs().map(|g| g.advance_width() as i32).collect());
+        let min_origin_sb = min(glyphs().map(|g| g.bounding_box.2).collect());
+        let min_advance_sb = min(glyphs()
+            .map(|g| g.advance_width() as i32 - g.bounding_box.2 - g.bounding_box.0 as i32)
+            .collect());
+        let max_before_bl = max(glyphs()
+            .map(|g| g.bounding_box.3 + g.bounding_box.1 as i32)
+            .collect());
+        let min_after_bl = min(glyphs().map(|g| g.bounding_box.3).collect());
+
+        let mut metrics = [0; 12];
+        metrics[0] = i8_metric(ascent).ok_or_else(out_of_range)?;
+        metrics[1] = i8_metric(-descent).ok_or_else(out_of_range)?;
+        metrics[2] = u8_metric(width_max).ok_or_else(out_of_range)?;
+        // Upright caret with a slope of 1 / 0
+        metrics[3] = 1;
+        metrics[4] = 0;
+        metrics[6] = i8_metric(min_origin_sb).ok_or_else(out_of_range)?;
+        metrics[7] = i8_metric(min_advance_sb).ok_or_else(out_of_range)?;
+        metrics[8] = i8_metric(max_before_bl).ok_or_else(out_of_range)?;
+        metrics[9] = i8_metric(min_after_bl).ok_or_else(out_of_range)?;
+
+        Ok(metrics)
+    }
+}
+
+fn u8_metric(value: i32) -> Option<u8> {
+    u8::try_from(value).ok()
+}
+
+fn i8_metric(value: i32) -> Option<u8> {
+    i8::try_from(value).ok().map(|value| value as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "ttf"
+SIZE 8 72 72
+FONTBOUNDINGBOX 8 8 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 6
+FONT_DESCENT 2
+ENDPROPERTIES
+STARTCHAR A
+ENCODING 65
+DWIDTH 6 0
+BBX 4 2 1 0
+BITMAP
+60
+90
+ENDCHAR
+STARTCHAR g
+ENCODING 103
+DWIDTH 6 0
+BBX 3 1 0 -2
+BITMAP
+E0
+ENDCHAR
+ENDFONT
+"#;
+
+    #[test]
+    fn it_exports_bitmap_tables() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+        let (ebdt, eblc) = font.to_truetype_bitmap_tables().unwrap();
+
+        assert_eq!(
+            ebdt,
+            vec![
+                0, 2, 0, 0, // version
+                2, 4, 1, 2, 6, 0x60, 0x90, // A
+                1, 3, 0, 0xff, 6, 0xe0, // g
+            ]
+        );
+
+        // Header and the start of the BitmapSize record
+        assert_eq!(
+            &eblc[0..20],
+            &[0, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 56, 0, 0, 0, 28, 0, 0, 0, 1]
+        );
+        // Horizontal line metrics
+        assert_eq!(&eblc[24..36], &[6, 0xfe, 6, 1, 0, 0, 0, 1, 2, 0xfe, 0, 0]);
+        // Glyph range, ppem, bit depth and flags
+        assert_eq!(&eblc[48..56], &[0, 0, 0, 1, 8, 8, 1, 1]);
+        // IndexSubTableArray and IndexSubTable
+        assert_eq!(
+            &eblc[56..],
+            &[
+                0, 0, 0, 1, 0, 0, 0, 8, // array
+                0, 1, 0, 1, 0, 0, 0, 4, // subtable header
+                0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 13, // offsets
+            ]
+        );
+    }
+
+    #[test]
+    fn it_rejects_fonts_without_glyphs() {
+        let font = BDFParser::from_str(
+            "STARTFONT 2.1\nFONT \"x\"\nSIZE 8 72 72\nFONTBOUNDINGBOX 8 8 0 0\nENDFONT\n",
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.to_truetype_bitmap_tables(),
+            Err(TtfExportError::NoGlyphs)
+        );
+    }
+}
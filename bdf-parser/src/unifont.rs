@@ -0,0 +1,126 @@
+use super::glyph::pack_bitmap;
+use super::{BDFFont, BdfParseError, Glyph};
+
+/// Height of all glyphs in the Unifont hex format.
+const HEIGHT: u32 = 16;
+
+/// Offset of the Unifont baseline from the bottom of the glyph.
+const Y_OFFSET: i32 = -2;
+
+impl BDFFont {
+    /// Parses a font in the GNU Unifont hex format.
+    ///
+    /// Every line contains a hexadecimal codepoint and the hexadecimal bitmap separated by a
+    /// colon. Bitmaps with 32 hex digits are 8x16 glyphs, bitmaps with 64 hex digits are 16x16
+    /// glyphs. The hex format has no header, so the returned font has no metadata.
+    pub fn from_unifont_hex(src: &str) -> Result<BDFFont, BdfParseError> {
+        let mut glyphs = Vec::new();
+        let mut offset = 0;
+
+        for line in src.split('\n') {
+            let line_offset = offset;
+            offset += line.len() + 1;
+
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let error = |column: usize, message: &str| BdfParseError::Parse {
+                offset: line_offset + column,
+                message: message.to_string(),
+            };
+
+            let colon = line.find(':').ok_or_else(|| error(0, "Missing colon"))?;
+            let (codepoint, data) = (&line[..colon], &line[colon + 1..]);
+
+            let charcode = u32::from_str_radix(codepoint, 16)
+                .ok()
+                .filter(|&c| c <= i32::MAX as u32)
+                .ok_or_else(|| error(0, "Invalid codepoint"))?;
+
+            let width = match data.len() {
+                32 => 8,
+                64 => 16,
+                _ => return Err(error(colon + 1, "Invalid bitmap length")),
+            };
+
+            let bytes = (0..data.len())
+                .step_by(2)
+                .map(|i| {
+                    data.get(i..i + 2)
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                })
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(|| error(colon + 1, "Invalid bitmap"))?;
+
+            glyphs.push(Glyph {
+                name: format!("U+{:04X}", charcode),
+                charcode: charcode as i32,
+                device_width: Some((width, 0)),
+                bounding_box: (width, HEIGHT, 0, Y_OFFSET),
+                bitmap: pack_bitmap(&bytes),
+                bitmap_rows: HEIGHT,
+            });
+        }
+
+        Ok(BDFFont {
+            metadata: None,
+            properties: None,
+            chars: Some(glyphs.len() as u32),
+            glyphs,
+            endfont: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "0041:0000000018242442427E424242420000\r
+\r
+AC00:0000000000000000000000000000000000000000000000000000000000000001\r
+";
+
+    #[test]
+    fn it_parses_unifont_hex() {
+        let font = BDFFont::from_unifont_hex(HEX).unwrap();
+        assert_eq!(font.glyphs.len(), 2);
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!(a.name, "U+0041");
+        assert_eq!(a.bounding_box, (8, 16, 0, -2));
+        assert_eq!(a.device_width, Some((8, 0)));
+        assert_eq!(a.get_pixel(3, 4), Some(true));
+        assert_eq!(a.get_pixel(0, 4), Some(false));
+
+        let ga = font.glyph('\u{AC00}').unwrap();
+        assert_eq!(ga.bounding_box, (16, 16, 0, -2));
+        assert_eq!(ga.get_pixel(15, 15), Some(true));
+        assert_eq!(ga.count_set_pixels(), 1);
+    }
+
+    #[test]
+    fn it_reports_invalid_lines() {
+        let offset = |src| match BDFFont::from_unifont_hex(src) {
+            Err(BdfParseError::Parse { offset, message }) => (offset, message),
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        assert_eq!(offset("0041"), (0, "Missing colon".to_string()));
+        assert_eq!(offset("\nXYZ:00"), (1, "Invalid codepoint".to_string()));
+        assert_eq!(
+            offset("0041:0000"),
+            (5, "Invalid bitmap length".to_string())
+        );
+        assert_eq!(
+            offset("0041:000000001824244242ZZ424242420000"),
+            (5, "Invalid bitmap".to_string())
+        );
+        assert_eq!(
+            offset("0041:00000000182424424\u{e4}2424242420000"),
+            (5, "Invalid bitmap".to_string())
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use super::BDFFont;
+
+/// Weight of a font, with the same classes as the `usWeightClass` field in OpenType fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeightClass {
+    Thin,
+    ExtraLight,
+    Light,
+    Regular,
+    Medium,
+    SemiBold,
+    Bold,
+    ExtraBold,
+    Black,
+    /// The weight is missing or has an unknown name.
+    Unknown,
+}
+
+impl WeightClass {
+    /// Parses a weight name like `Bold` or `demi bold`, ignoring case, spaces and dashes.
+    fn from_name(name: &str) -> Self {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        match name.as_str() {
+            "thin" | "hairline" => WeightClass::Thin,
+            "extralight" | "ultralight" => WeightClass::ExtraLight,
+            "light" => WeightClass::Light,
+            "regular" | "normal" | "book" | "roman" => WeightClass::Regular,
+            "medium" => WeightClass::Medium,
+            "semibold" | "demibold" | "demi" => WeightClass::SemiBold,
+            "bold" => WeightClass::Bold,
+            "extrabold" | "ultrabold" => WeightClass::ExtraBold,
+            "black" | "heavy" => WeightClass::Black,
+            _ => WeightClass::Unknown,
+        }
+    }
+}
+
+impl BDFFont {
+    /// Returns the weight of the font.
+    ///
+    /// The weight is read from the `WEIGHT_NAME` property or, if the property is missing or
+    /// unknown, from the weight field of the XLFD font name.
+    pub fn weight_class(&self) -> WeightClass {
+        let from_property = self
+            .text_property("WEIGHT_NAME")
+            .map_or(WeightClass::Unknown, WeightClass::from_name);
+        if from_property != WeightClass::Unknown {
+            return from_property;
+        }
+
+        // XLFD names start with a dash, which makes the weight the fourth field
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.name.trim_matches('"'))
+            .filter(|name| name.starts_with('-'))
+            .and_then(|name| name.split('-').nth(3))
+            .map_or(WeightClass::Unknown, WeightClass::from_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {BDFParser, PropertyValue};
+
+    fn font(name: &str) -> BDFFont {
+        let source = format!(
+            "STARTFONT 2.1\nFONT {}\nSIZE 8 75 75\nFONTBOUNDINGBOX 8 8 0 0\nENDFONT\n",
+            name
+        );
+
+        BDFParser::from_str(&source).parse().unwrap().1
+    }
+
+    #[test]
+    fn it_parses_weight_names() {
+        assert_eq!(WeightClass::from_name("Bold"), WeightClass::Bold);
+        assert_eq!(WeightClass::from_name("demi bold"), WeightClass::SemiBold);
+        assert_eq!(
+            WeightClass::from_name("Extra-Light"),
+            WeightClass::ExtraLight
+        );
+        assert_eq!(WeightClass::from_name("Heavy"), WeightClass::Black);
+        assert_eq!(WeightClass::from_name(""), WeightClass::Unknown);
+        assert_eq!(WeightClass::from_name("wide"), WeightClass::Unknown);
+    }
+
+    #[test]
+    fn it_reads_the_weight_from_the_xlfd_name() {
+        assert_eq!(
+            font("-misc-fixed-bold-r-normal--13-120-75-75-c-70-iso10646-1").weight_class(),
+            WeightClass::Bold
+        );
+        assert_eq!(
+            font("-adobe-helvetica-medium-o-normal--12-120-75-75-p-67-iso8859-1").weight_class(),
+            WeightClass::Medium
+        );
+        assert_eq!(font("\"fixed-bold\"").weight_class(), WeightClass::Unknown);
+    }
+
+    #[test]
+    fn it_prefers_the_weight_name_property() {
+        let mut font = font("-misc-fixed-bold-r-normal--13-120-75-75-c-70-iso10646-1");
+        font.properties = Some(hashmap! {
+            "WEIGHT_NAME".into() => PropertyValue::Text("Light".into()),
+        });
+        assert_eq!(font.weight_class(), WeightClass::Light);
+
+        font.properties = Some(hashmap! {
+            "WEIGHT_NAME".into() => PropertyValue::Text("".into()),
+        });
+        assert_eq!(font.weight_class(), WeightClass::Bold);
+    }
+}
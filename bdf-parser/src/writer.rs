@@ -0,0 +1,240 @@
+use std::io::{self, Write};
+
+use super::metadata::FontSize;
+use super::{BDFFont, Glyph, Metadata, PropertyValue};
+
+impl BDFFont {
+    /// Writes the font in the BDF format.
+    ///
+    /// The output is written line by line without collecting it in memory first, so `w` should
+    /// be buffered. Properties are sorted by name to make the output reproducible. Fonts without
+    /// metadata get a header with the union of the glyph bounding boxes, a point size equal to
+    /// its height and a resolution of 72 DPI.
+    pub fn serialize_to_writer<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let metadata = self
+            .metadata
+            .clone()
+            .unwrap_or_else(|| self.default_metadata());
+        let (point_size, x_resolution, y_resolution) = metadata.size;
+        let (width, height, x, y) = metadata.bounding_box;
+
+        writeln!(w, "STARTFONT {:.1}", metadata.version)?;
+        writeln!(w, "FONT {}", metadata.name)?;
+        writeln!(w, "SIZE {} {} {}", point_size, x_resolution, y_resolution)?;
+        writeln!(w, "FONTBOUNDINGBOX {} {} {} {}", width, height, x, y)?;
+
+        if let Some(ref properties) = self.properties {
+            let mut properties: Vec<_> = properties.iter().collect();
+            properties.sort_by_key(|&(name, _)| name);
+
+            writeln!(w, "STARTPROPERTIES {}", properties.len())?;
+            for (name, value) in properties {
+                match value {
+                    PropertyValue::Text(text) => {
+                        // Multi-line values are written with tab-indented continuation lines
+                        let text = text.replace('"', "\"\"").replace('\n', "\n\t");
+                        writeln!(w, "{} \"{}\"", name, text)?
+                    }
+                    PropertyValue::Int(value) => writeln!(w, "{} {}", name, value)?,
+                }
+            }
+            writeln!(w, "ENDPROPERTIES")?;
+        }
+
+        writeln!(w, "CHARS {}", self.glyphs.len())?;
+
+        for glyph in &self.glyphs {
+            write_glyph(glyph, Some(metadata.size), w)?;
+        }
+
+        writeln!(w, "ENDFONT")
+    }
+
+    /// Writes a single glyph in the BDF format, from `STARTCHAR` to `ENDCHAR`.
+    ///
+    /// This allows glyphs to be streamed into a font without building a `BDFFont` first. The
+    /// `SWIDTH` line is left out, because it depends on the point size and resolution of the
+    /// font, which the glyph doesn't contain.
+    pub fn write_glyph_to_bdf<W: Write>(glyph: &Glyph, w: &mut W) -> io::Result<()> {
+        write_glyph(glyph, None, w)
+    }
+
+    fn default_metadata(&self) -> Metadata {
+        let bounds = self.glyphs.iter().fold(None, |bounds, glyph| {
+            let (width, height, x, y) = glyph.bounding_box;
+            let (right, top) = (x + width as i32, y + height as i32);
+
+            Some(match bounds {
+                Some((left, bottom, r, t)) => {
+                    (x.min(left), y.min(bottom), right.max(r), top.max(t))
+                }
+                None => (x, y, right, top),
+            })
+        });
+        let (left, bottom, right, top) = bounds.unwrap_or((0, 0, 0, 0));
+        let (width, height) = ((right - left) as u32, (top - bottom) as u32);
+
+        Metadata::builder()
+            .name("unnamed")
+            .point_size(height as i32)
+            .resolution(72, 72)
+            .bounding_box((width, height, left, bottom))
+            .build()
+    }
+}
+
+/// Writes a glyph and computes its `SWIDTH` from `size`, if it is given.
+///
+/// The `SWIDTH` is 0 if the point size or resolution is 0. Glyphs without pixels are written
+/// with a `BBX` of `0 0`, because the number of bitmap rows must match the height.
+fn write_glyph<W: Write>(glyph: &Glyph, size: Option<FontSize>, w: &mut W) -> io::Result<()> {
+    let (width, height, x, y) = match glyph.bounding_box {
+        (0, _, x, y) | (_, 0, x, y) => (0, 0, x, y),
+        bounding_box => bounding_box,
+    };
+
+    writeln!(w, "STARTCHAR {}", glyph.name)?;
+    writeln!(w, "ENCODING {}", glyph.charcode)?;
+    if let Some((point_size, x_resolution, y_resolution)) = size {
+        let (dx, dy) = glyph.device_width.unwrap_or((glyph.advance_width(), 0));
+        writeln!(
+            w,
+            "SWIDTH {} {}",
+            scalable_width(dx, point_size, x_resolution),
+            scalable_width(dy, point_size, y_resolution)
+        )?;
+    }
+    if let Some((dx, dy)) = glyph.device_width {
+        writeln!(w, "DWIDTH {} {}", dx, dy)?;
+    }
+    writeln!(w, "BBX {} {} {} {}", width, height, x, y)?;
+    writeln!(w, "BITMAP")?;
+
+    let stride = glyph.stride();
+    if stride > 0 {
+        for row in glyph.bitmap_bytes().chunks(stride) {
+            for byte in row {
+                write!(w, "{:02X}", byte)?;
+            }
+            writeln!(w)?;
+        }
+    }
+
+    writeln!(w, "ENDCHAR")
+}
+
+/// Converts a device width in pixels to a scalable width in 1/1000 of the point size.
+fn scalable_width(device_width: u32, point_size: i32, resolution: u32) -> u64 {
+    let scale = u64::from(point_size.max(0) as u32) * u64::from(resolution);
+    if scale == 0 {
+        return 0;
+    }
+
+    (u64::from(device_width) * 72_000 + scale / 2) / scale
+}
+
+#[cfg(test)]
+mod tests {
+    use {BDFFont, BDFParser};
+
+    const FONT: &str = r#"STARTFONT 2.1
+FONT "writer"
+SIZE 8 75 75
+FONTBOUNDINGBOX 12 2 0 -1
+STARTPROPERTIES 2
+COPYRIGHT "Public ""domain""
+	No rights reserved"
+FONT_ASCENT 1
+ENDPROPERTIES
+CHARS 2
+STARTCHAR A
+ENCODING 65
+SWIDTH 1440 0
+DWIDTH 12 0
+BBX 12 2 0 -1
+BITMAP
+FFF0
+8010
+ENDCHAR
+STARTCHAR empty
+ENCODING -1
+SWIDTH 0 0
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#;
+
+    #[test]
+    fn it_serializes_fonts() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+
+        let mut out = Vec::new();
+        font.serialize_to_writer(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out, FONT);
+        assert_eq!(BDFParser::from_str(&out).parse().unwrap().1, font);
+        assert_eq!(
+            font.text_property("COPYRIGHT"),
+            Some("Public \"domain\"\nNo rights reserved")
+        );
+    }
+
+    #[test]
+    fn it_writes_the_version_with_one_decimal_place() {
+        let source = FONT.replace("STARTFONT 2.1", "STARTFONT 2.0");
+        let font = BDFParser::from_str(&source).parse().unwrap().1;
+
+        let mut out = Vec::new();
+        font.serialize_to_writer(&mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), source);
+    }
+
+    #[test]
+    fn it_writes_a_header_for_fonts_without_metadata() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1.drop_metadata();
+
+        let mut out = Vec::new();
+        font.serialize_to_writer(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out
+            .starts_with("STARTFONT 2.1\nFONT unnamed\nSIZE 2 72 72\nFONTBOUNDINGBOX 12 2 0 -1\n"));
+        assert!(out.contains("ENCODING 65\nSWIDTH 6000 0\nDWIDTH 12 0\n"));
+
+        let parsed = BDFParser::from_str(&out).parse_font().unwrap();
+        assert_eq!(parsed.glyphs, font.glyphs);
+        assert_eq!(parsed.properties, font.properties);
+    }
+
+    #[test]
+    fn it_writes_single_glyphs() {
+        let font = BDFParser::from_str(FONT).parse().unwrap().1;
+
+        let mut out = Vec::new();
+        BDFFont::write_glyph_to_bdf(font.glyph('A').unwrap(), &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "STARTCHAR A\nENCODING 65\nDWIDTH 12 0\nBBX 12 2 0 -1\nBITMAP\nFFF0\n8010\nENDCHAR\n"
+        );
+    }
+
+    #[test]
+    fn it_writes_empty_bounding_boxes_for_glyphs_without_pixels() {
+        let source = FONT.replace("BBX 0 0 0 0", "BBX 0 4 0 -1");
+        let font = BDFParser::from_str(&source).parse().unwrap().1;
+
+        let mut out = Vec::new();
+        font.serialize_to_writer(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out
+            .contains("STARTCHAR empty\nENCODING -1\nSWIDTH 0 0\nBBX 0 0 0 -1\nBITMAP\nENDCHAR\n"));
+
+        let parsed = BDFParser::from_str(&out).parse_font().unwrap();
+        assert!(parsed.verify_compliance().errors.is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::Write;
+
+use super::BDFFont;
+
+/// Escapes the characters that aren't allowed in XML attribute values.
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+impl BDFFont {
+    /// Returns a minimal XML representation of the font.
+    ///
+    /// Every glyph is written as a `glyph` element with its encoding, name, advance width and
+    /// bounding box as attributes and one `row` element per bitmap row, which contains the row
+    /// as hex digits like in a BDF file. Unencoded glyphs have the encoding -1.
+    pub fn to_simplified_font_xml(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+
+        match self.metadata {
+            Some(ref metadata) => {
+                let (point_size, x_resolution, y_resolution) = metadata.size;
+                let name = metadata.name.trim_matches('"');
+
+                writeln!(
+                    out,
+                    r#"<font name="{}" size="{}" xres="{}" yres="{}">"#,
+                    escape(name),
+                    point_size,
+                    x_resolution,
+                    y_resolution
+                )
+                .unwrap();
+            }
+            None => writeln!(out, "<font>").unwrap(),
+        }
+
+        for glyph in &self.glyphs {
+            let (width, height, x, y) = glyph.bounding_box;
+
+            writeln!(
+                out,
+                r#"  <glyph encoding="{}" name="{}" width="{}" bbx="{} {} {} {}">"#,
+                glyph.charcode,
+                escape(&glyph.name),
+                glyph.advance_width(),
+                width,
+                height,
+                x,
+                y
+            )
+            .unwrap();
+
+            let stride = glyph.stride();
+            if stride > 0 {
+                for row in glyph.bitmap_bytes().chunks(stride) {
+                    out.push_str("    <row>");
+                    for byte in row {
+                        write!(out, "{:02X}", byte).unwrap();
+                    }
+                    out.push_str("</row>\n");
+                }
+            }
+
+            writeln!(out, "  </glyph>").unwrap();
+        }
+
+        writeln!(out, "</font>").unwrap();
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BDFParser;
+
+    #[test]
+    fn it_escapes_attribute_values() {
+        assert_eq!(escape(r#"a<b>&"c'"#), "a&lt;b&gt;&amp;&quot;c&apos;");
+    }
+
+    #[test]
+    fn it_exports_simplified_xml() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "xml"
+SIZE 8 75 75
+FONTBOUNDINGBOX 12 2 0 -1
+STARTCHAR A
+ENCODING 65
+DWIDTH 13 0
+BBX 12 2 0 -1
+BITMAP
+FFF0
+8010
+ENDCHAR
+STARTCHAR a&b
+ENCODING -1
+BBX 0 0 0 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.to_simplified_font_xml(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<font name="xml" size="8" xres="75" yres="75">
+  <glyph encoding="65" name="A" width="13" bbx="12 2 0 -1">
+    <row>FFF0</row>
+    <row>8010</row>
+  </glyph>
+  <glyph encoding="-1" name="a&amp;b" width="0" bbx="0 0 0 0">
+  </glyph>
+</font>
+"#
+        );
+    }
+}
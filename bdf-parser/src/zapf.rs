@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use super::BDFFont;
+
+impl BDFFont {
+    /// Returns a table with the metrics of every glyph in the Zapf metrics style.
+    ///
+    /// Each line contains the encoding, the advance width, the bounding box height, the
+    /// distance from the top of the bounding box to the baseline and the glyph name, in fixed
+    /// width columns. All values are in pixels and unencoded glyphs have the code -1.
+    pub fn to_zapf_metrics(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            "{:>8} {:>6} {:>6} {:>8} NAME",
+            "CODE", "WIDTH", "HEIGHT", "BASELINE"
+        )
+        .unwrap();
+
+        for glyph in &self.glyphs {
+            let (_, height, _, y_offset) = glyph.bounding_box;
+
+            writeln!(
+                out,
+                "{:>8} {:>6} {:>6} {:>8} {}",
+                glyph.charcode,
+                glyph.advance_width(),
+                height,
+                height as i32 + y_offset,
+                glyph.name
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use BDFParser;
+
+    #[test]
+    fn it_exports_zapf_metrics() {
+        let font = BDFParser::from_str(
+            r#"STARTFONT 2.1
+FONT "zapf"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 10 0 -2
+STARTCHAR A
+ENCODING 65
+DWIDTH 7 0
+BBX 6 8 0 0
+BITMAP
+ENDCHAR
+STARTCHAR g
+ENCODING 103
+BBX 5 8 0 -2
+BITMAP
+ENDCHAR
+STARTCHAR ligature
+ENCODING -1
+DWIDTH 12 0
+BBX 10 8 1 0
+BITMAP
+ENDCHAR
+ENDFONT
+"#,
+        )
+        .parse()
+        .unwrap()
+        .1;
+
+        assert_eq!(
+            font.to_zapf_metrics(),
+            [
+                "    CODE  WIDTH HEIGHT BASELINE NAME",
+                "      65      7      8        8 A",
+                "     103      5      8        6 g",
+                "      -1     12      8        8 ligature",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -58,12 +58,10 @@ pub fn read(path: &Path) -> String {
     // decode file into utf-8
     let coder = encoding_from_whatwg_label(charset2encoding(&result.0));
 
-    let utf8reader = coder
+    coder
         .unwrap()
         .decode(&reader, DecoderTrap::Ignore)
-        .expect("Error");
-
-    utf8reader
+        .expect("Error")
 }
 
 pub fn test_font_parse(filepath: &Path) -> Result<(), String> {
@@ -81,6 +79,6 @@ pub fn test_font_parse(filepath: &Path) -> Result<(), String> {
                 Ok(())
             }
         }
-        _ => Err(format!("Other error")),
+        _ => Err("Other error".to_string()),
     }
 }
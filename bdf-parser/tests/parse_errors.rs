@@ -0,0 +1,36 @@
+extern crate bdf_parser;
+
+use bdf_parser::{BDFParser, BdfParseError};
+
+const FONT: &str = r#"STARTFONT 2.1
+FONT "errors"
+SIZE 8 75 75
+FONTBOUNDINGBOX 8 8 0 0
+STARTCHAR A
+ENCODING 65
+BBX 8 1 0 0
+BITMAP
+ff
+ENDCHAR
+ENDFONT
+"#;
+
+#[test]
+fn it_parses_valid_fonts() {
+    let font = BDFParser::from_str(FONT).parse_font().unwrap();
+
+    assert_eq!(font.glyph('A').unwrap().bitmap_bytes(), vec![0xff]);
+}
+
+#[test]
+fn it_returns_descriptive_parse_errors() {
+    let source = FONT.replace("ENCODING 65", "ENCODING x");
+
+    match BDFParser::from_str(&source).parse_font() {
+        Err(BdfParseError::Parse { offset, message }) => {
+            assert_eq!(offset, 86);
+            assert_eq!(message, "Expected integer in ENCODING line");
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}